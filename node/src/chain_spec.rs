@@ -4,18 +4,90 @@ use lunes_runtime::{
 	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
 	SystemConfig, WASM_BINARY, SessionConfig, StakingConfig,SessionKeys,
 	constants::currency::*, StakerStatus, Balance,IndicesConfig,
-	CouncilConfig,DemocracyConfig,TechnicalCommitteeConfig,
+	CouncilConfig,DemocracyConfig,TechnicalCommitteeConfig,NominationPoolsConfig,
+	ImOnlineConfig, AuthorityDiscoveryConfig, EVMChainIdConfig, VestingConfig, BlockNumber,
+	constants::time::DAYS, constants::{evm, pools},
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_core::{crypto::UncheckedInto, sr25519, Pair, Public};
+use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
+use sp_core::{
+	crypto::{Ss58Codec, UncheckedInto},
+	sr25519, Pair, Public,
+};
 use sc_telemetry::TelemetryEndpoints;
 use sp_runtime::{
 	traits::{IdentifyAccount, Verify},
 	Perbill,
 };
 
+/// A share of the genesis issuance that vests linearly once a cliff is reached.
+struct GenesisAllocation {
+	/// Environment variable giving the SS58 address of the beneficiary on the staging networks.
+	beneficiary_var: &'static str,
+	/// Seed of the beneficiary on the development networks.
+	dev_seed: &'static str,
+	/// Share of the genesis issuance.
+	share: Perbill,
+	/// Block before which nothing unlocks.
+	cliff: BlockNumber,
+	/// Number of blocks over which the allocation unlocks after the cliff.
+	duration: BlockNumber,
+}
+
+/// Team, foundation and ecosystem allocations, taken from what the root key receives.
+const GENESIS_ALLOCATIONS: [GenesisAllocation; 3] = [
+	// Team: one year cliff, then three years.
+	GenesisAllocation {
+		beneficiary_var: "LUNES_TEAM_ACCOUNT",
+		dev_seed: "Charlie",
+		share: Perbill::from_percent(15),
+		cliff: 365 * DAYS,
+		duration: 3 * 365 * DAYS,
+	},
+	// Foundation: six months cliff, then two years.
+	GenesisAllocation {
+		beneficiary_var: "LUNES_FOUNDATION_ACCOUNT",
+		dev_seed: "Dave",
+		share: Perbill::from_percent(20),
+		cliff: 182 * DAYS,
+		duration: 2 * 365 * DAYS,
+	},
+	// Ecosystem: three months cliff, then four years.
+	GenesisAllocation {
+		beneficiary_var: "LUNES_ECOSYSTEM_ACCOUNT",
+		dev_seed: "Eve",
+		share: Perbill::from_percent(25),
+		cliff: 91 * DAYS,
+		duration: 4 * 365 * DAYS,
+	},
+];
+
+/// The account receiving each of [`GENESIS_ALLOCATIONS`], in the same order.
+type Beneficiaries = [AccountId; GENESIS_ALLOCATIONS.len()];
+
+/// The beneficiaries of the development networks, derived from well-known seeds.
+fn dev_beneficiaries() -> Beneficiaries {
+	GENESIS_ALLOCATIONS
+		.map(|allocation| get_account_id_from_seed::<sr25519::Public>(allocation.dev_seed))
+}
+
+/// The beneficiaries of the staging networks, read from the environment when the spec is built.
+fn beneficiaries_from_env() -> Result<Beneficiaries, String> {
+	let mut beneficiaries = vec![];
+	for allocation in &GENESIS_ALLOCATIONS {
+		let var = allocation.beneficiary_var;
+		let address = std::env::var(var)
+			.map_err(|_| format!("{var} must be set to the SS58 address of its beneficiary"))?;
+		let account = AccountId::from_ss58check(&address)
+			.map_err(|e| format!("{var} is not a valid SS58 address: {e:?}"))?;
+		beneficiaries.push(account);
+	}
+	Ok(beneficiaries.try_into().expect("one beneficiary per allocation; qed"))
+}
+
 // The URL for the telemetry server.
 const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
@@ -33,8 +105,20 @@ type AccountPublic = <Signature as Verify>::Signer;
 fn session_keys(
 	aura: AuraId,
 	grandpa: GrandpaId,
+	im_online: ImOnlineId,
+	authority_discovery: AuthorityDiscoveryId,
 ) -> SessionKeys {
-	SessionKeys { aura, grandpa }
+	SessionKeys { aura, grandpa, im_online, authority_discovery }
+}
+
+/// Extends the keys of an authority registered before im-online and authority discovery were
+/// added. Like the runtime migration of the session keys, it reuses the sr25519 Aura key for both
+/// until the validator rotates its keys with `author_rotateKeys` and `session.setKeys`.
+fn with_legacy_keys(
+	(account, aura, grandpa): (AccountId, AuraId, GrandpaId),
+) -> (AccountId, AuraId, GrandpaId, ImOnlineId, AuthorityDiscoveryId) {
+	let key = sr25519::Public::from(aura.clone());
+	(account, aura, grandpa, key.into(), key.into())
 }
 
 /// Generate an account ID from seed.
@@ -45,9 +129,17 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-/// Generate an Aura authority key.
-pub fn authority_keys_from_seed(s: &str) -> (AccountId,AuraId, GrandpaId) {
-	(get_account_id_from_seed::<sr25519::Public>(s),get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
+/// Generate the session keys of an authority.
+pub fn authority_keys_from_seed(
+	s: &str,
+) -> (AccountId, AuraId, GrandpaId, ImOnlineId, AuthorityDiscoveryId) {
+	(
+		get_account_id_from_seed::<sr25519::Public>(s),
+		get_from_seed::<AuraId>(s),
+		get_from_seed::<GrandpaId>(s),
+		get_from_seed::<ImOnlineId>(s),
+		get_from_seed::<AuthorityDiscoveryId>(s),
+	)
 }
 
 pub fn development_config() -> Result<ChainSpec, String> {
@@ -77,6 +169,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				// Pre-funded accounts
 				balances.clone(),
+				// Vested allocations
+				dev_beneficiaries(),
 				true,
 			)
 		},
@@ -115,6 +209,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				// Pre-funded accounts
 				balances.clone(),
+				// Vested allocations
+				dev_beneficiaries(),
 				true,
 			)
 		},
@@ -132,17 +228,18 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	))
 }
 
-pub fn staging_network_config() -> ChainSpec {
+pub fn staging_network_config() -> Result<ChainSpec, String> {
+	let beneficiaries = beneficiaries_from_env()?;
 	let boot_nodes = vec![];
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "LUNES".into());
 	properties.insert("tokenDecimals".into(), 8.into());
 	properties.insert("ss58Format".into(), 57.into());
-	ChainSpec::from_genesis(
+	Ok(ChainSpec::from_genesis(
 		"Lunes Nigthly",
 		"lunes",
 		ChainType::Live,
-		staging_network_config_genesis,
+		move || staging_network_config_genesis(beneficiaries.clone()),
 		boot_nodes,
 		Some(
 			TelemetryEndpoints::new(vec![(STAGING_TELEMETRY_URL.to_string(), 0)])
@@ -154,20 +251,21 @@ pub fn staging_network_config() -> ChainSpec {
 		// Properties
 		Some(properties),
 		Default::default(),
-	)
+	))
 }
 
-pub fn staging_testnet_network_config() -> ChainSpec {
+pub fn staging_testnet_network_config() -> Result<ChainSpec, String> {
+	let beneficiaries = beneficiaries_from_env()?;
 	let boot_nodes: Vec<sc_network::config::MultiaddrWithPeerId> = vec![];
 	let mut properties = sc_chain_spec::Properties::new();
 	properties.insert("tokenSymbol".into(), "LUNES".into());
 	properties.insert("tokenDecimals".into(), 8.into());
 	properties.insert("ss58Format".into(), 57.into());
-	ChainSpec::from_genesis(
+	Ok(ChainSpec::from_genesis(
 		"Lunes Nigthly",
 		"testnet_node",
 		ChainType::Live,
-		staging_test_network_config_genesis,
+		move || staging_test_network_config_genesis(beneficiaries.clone()),
 		boot_nodes,
 		Some(
 			TelemetryEndpoints::new(vec![(STAGING_TELEMETRY_URL.to_string(), 0)])
@@ -179,10 +277,10 @@ pub fn staging_testnet_network_config() -> ChainSpec {
 		// Properties
 		Some(properties),
 		Default::default(),
-	)
+	))
 }
 
-fn staging_network_config_genesis() -> GenesisConfig {
+fn staging_network_config_genesis(beneficiaries: Beneficiaries) -> GenesisConfig {
 	let wasm_binary = WASM_BINARY.expect(
 		"Development wasm binary is not available. This means the client is built with \
 		 `SKIP_WASM_BUILD` flag and it is only usable for production chains. Please rebuild with \
@@ -243,14 +341,15 @@ fn staging_network_config_genesis() -> GenesisConfig {
 
 	mainnet_genesis(
 		wasm_binary,
-		initial_authorities,
+		initial_authorities.into_iter().map(with_legacy_keys).collect(),
 		root_key,
 		endowed_accounts.clone(),
+		beneficiaries,
 		false,
 	)
 }
 
-fn staging_test_network_config_genesis() -> GenesisConfig {
+fn staging_test_network_config_genesis(beneficiaries: Beneficiaries) -> GenesisConfig {
 	let wasm_binary = WASM_BINARY.expect(
 		"Development wasm binary is not available. This means the client is built with \
 		 `SKIP_WASM_BUILD` flag and it is only usable for production chains. Please rebuild with \
@@ -292,9 +391,10 @@ fn staging_test_network_config_genesis() -> GenesisConfig {
 
 	mainnet_genesis(
 		wasm_binary,
-		initial_authorities,
+		initial_authorities.into_iter().map(with_legacy_keys).collect(),
 		root_key,
 		endowed_accounts.clone(),
+		beneficiaries,
 		true,
 	)
 }
@@ -306,9 +406,12 @@ fn mainnet_genesis(
 		AccountId,
 		AuraId,
 		GrandpaId,
+		ImOnlineId,
+		AuthorityDiscoveryId,
 	)>,
 	root_key: AccountId,
 	mut endowed_accounts: Vec<(AccountId , Balance)>,
+	beneficiaries: Beneficiaries,
 	_enable_testnet: bool,
 ) -> GenesisConfig {
 	// endow all authorities and nominators.
@@ -317,9 +420,19 @@ fn mainnet_genesis(
 		genesis_issuance = TOTAL_INITIAL_ISSUANCE_LUNES_TEST;
 	}
 
+	let total_issuance = genesis_issuance;
 	for balance in endowed_accounts.clone() {
 		genesis_issuance -= balance.1;
 	}
+	let mut allocations = vec![];
+	let mut vesting = vec![];
+	for (allocation, account) in GENESIS_ALLOCATIONS.iter().zip(beneficiaries) {
+		assert!(account != root_key, "{} must not be the sudo key", allocation.beneficiary_var);
+		let amount = allocation.share * total_issuance;
+		genesis_issuance -= amount;
+		allocations.push((account.clone(), amount));
+		vesting.push((account, allocation.cliff, allocation.duration, 0));
+	}
 	let stakers = initial_authorities
 		.iter()
 		.map(|x| (x.0.clone(), x.0.clone(), MIN_VALIDATOR_BOND, StakerStatus::Validator))
@@ -336,9 +449,11 @@ fn mainnet_genesis(
 			code: wasm_binary.to_vec(),
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			balances: endowed_accounts,
+			// The root key receives what is left of the issuance once the allocations, which are
+			// locked by `vesting` below, are taken out.
+			balances: endowed_accounts.into_iter().chain(allocations).collect(),
 		},
+		vesting: VestingConfig { vesting },
 		indices: IndicesConfig { indices: vec![] },
 		aura: AuraConfig {
 			authorities: vec![],
@@ -353,7 +468,7 @@ fn mainnet_genesis(
 					(
 						x.0.clone(),
 						x.0.clone(),
-						session_keys(x.1.clone(), x.2.clone()),
+						session_keys(x.1.clone(), x.2.clone(), x.3.clone(), x.4.clone()),
 					)
 				})
 				.collect::<Vec<_>>(),
@@ -368,6 +483,21 @@ fn mainnet_genesis(
 			stakers,
 			..Default::default()
 		},
+		nomination_pools: NominationPoolsConfig {
+			min_create_bond: MIN_POOL_CREATE_BOND,
+			min_join_bond: MIN_POOL_JOIN_BOND,
+			max_pools: Some(pools::MAX_POOLS),
+			max_members_per_pool: Some(pools::MAX_MEMBERS_PER_POOL),
+			max_members: Some(pools::MAX_MEMBERS),
+			..Default::default()
+		},
+		im_online: ImOnlineConfig { keys: vec![] },
+		authority_discovery: AuthorityDiscoveryConfig { keys: vec![] },
+		evm: Default::default(),
+		ethereum: Default::default(),
+		evm_chain_id: EVMChainIdConfig {
+			chain_id: if _enable_testnet { evm::TESTNET_CHAIN_ID } else { evm::CHAIN_ID },
+		},
 		democracy: DemocracyConfig::default(),
 		council: CouncilConfig::default(),
 		technical_committee: TechnicalCommitteeConfig {
@@ -1,4 +1,38 @@
+use crate::eth::EthConfiguration;
 use sc_cli::RunCmd;
+use std::str::FromStr;
+
+/// Block sealing strategy for development nodes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only on `engine_createBlock` RPC calls.
+	Manual,
+	/// Seal a block every given number of milliseconds, at least one.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Ok(match s {
+			"instant" => Self::Instant,
+			"manual" => Self::Manual,
+			millis => match millis.parse() {
+				Ok(0) => return Err("the block interval must be at least 1 millisecond".into()),
+				Ok(millis) => Self::Interval(millis),
+				Err(_) => {
+					return Err(format!(
+						"`{}` is not `instant`, `manual` or a block interval in milliseconds",
+						s
+					))
+				},
+			},
+		})
+	}
+}
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -7,6 +41,16 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Replace Aura + GRANDPA with manual sealing: `instant`, `manual` or an interval in ms.
+	///
+	/// Blocks can always be sealed and finalized through the `engine_createBlock` and
+	/// `engine_finalizeBlock` RPCs. Only meant for development chains.
+	#[arg(long)]
+	pub sealing: Option<Sealing>,
+
+	#[command(flatten)]
+	pub eth: EthConfiguration,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -42,8 +42,8 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()?),
 			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
-			"staging" => Box::new(chain_spec::staging_network_config()),
-			"staging-test" => Box::new(chain_spec::staging_testnet_network_config()),
+			"staging" => Box::new(chain_spec::staging_network_config()?),
+			"staging-test" => Box::new(chain_spec::staging_testnet_network_config()?),
 			path =>
 				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
 		})
@@ -210,8 +210,14 @@ pub fn run() -> sc_cli::Result<()> {
 		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let sealing = cli.sealing;
+			let eth_config = cli.eth.clone();
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config).map_err(sc_cli::Error::Service)
+				match sealing {
+					Some(sealing) => service::new_manual_seal(config, sealing, &eth_config),
+					None => service::new_full(config, &eth_config),
+				}
+				.map_err(sc_cli::Error::Service)
 			})
 		},
 	}
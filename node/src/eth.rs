@@ -0,0 +1,205 @@
+//! Frontier: the database mapping Ethereum blocks and transactions to LUNES blocks, and the
+//! background tasks behind the Ethereum RPCs.
+
+use std::{
+	collections::BTreeMap,
+	path::PathBuf,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+use fc_mapping_sync::{MappingSyncWorker, SyncStrategy};
+use fc_rpc::{EthBlockDataCacheTask, EthTask, OverrideHandle};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use futures::{future, StreamExt};
+use node_primitives::{Block, Hash};
+use sc_client_api::BlockchainEvents;
+use sc_network::NetworkService;
+use sc_network_sync::SyncingService;
+use sc_service::{error::Error as ServiceError, BasePath, Configuration, TaskManager};
+use sc_transaction_pool::FullChainApi;
+
+use crate::{
+	rpc::eth::EthDeps,
+	service::{FullBackend, FullClient, FullPool},
+};
+
+/// Options of the Ethereum RPCs.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct EthConfiguration {
+	/// Serve the `eth_*`, `net_*` and `web3_*` RPCs and keep the Frontier database behind them.
+	///
+	/// The database is filled in the background with the blocks imported before it was enabled.
+	#[arg(long)]
+	pub enable_eth_rpc: bool,
+
+	/// Maximum number of logs returned by a single `eth_getLogs` query.
+	#[arg(long, default_value = "10000")]
+	pub max_past_logs: u32,
+
+	/// Maximum number of blocks kept in the `eth_feeHistory` cache.
+	#[arg(long, default_value = "2048")]
+	pub fee_history_limit: u64,
+
+	/// `eth_call` and `eth_estimateGas` may use up to this many times the block gas limit.
+	#[arg(long, default_value = "10")]
+	pub execute_gas_limit_multiplier: u64,
+
+	/// Size in bytes of the LRU cache for block data.
+	#[arg(long, default_value = "50")]
+	pub eth_log_block_cache: usize,
+
+	/// Size in bytes of the LRU cache for transaction statuses.
+	#[arg(long, default_value = "50")]
+	pub eth_statuses_cache: usize,
+}
+
+/// The Frontier parts of a running node, shared by the RPCs.
+#[derive(Clone)]
+pub struct Frontier {
+	backend: Arc<fc_db::Backend<Block>>,
+	overrides: Arc<OverrideHandle<Block>>,
+	block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+	filter_pool: FilterPool,
+	fee_history_cache: FeeHistoryCache,
+	fee_history_cache_limit: FeeHistoryCacheLimit,
+	max_past_logs: u32,
+	execute_gas_limit_multiplier: u64,
+}
+
+impl Frontier {
+	/// Dependencies of the Ethereum RPCs.
+	pub fn rpc_deps(
+		&self,
+		client: Arc<FullClient>,
+		pool: Arc<FullPool>,
+		network: Arc<NetworkService<Block, Hash>>,
+		sync: Arc<SyncingService<Block>>,
+		is_authority: bool,
+	) -> EthDeps<FullClient, FullPool, FullChainApi<FullClient, Block>> {
+		EthDeps {
+			client,
+			graph: pool.pool().clone(),
+			pool,
+			is_authority,
+			network,
+			sync,
+			frontier_backend: self.backend.clone(),
+			overrides: self.overrides.clone(),
+			block_data_cache: self.block_data_cache.clone(),
+			filter_pool: self.filter_pool.clone(),
+			max_past_logs: self.max_past_logs,
+			fee_history_cache: self.fee_history_cache.clone(),
+			fee_history_cache_limit: self.fee_history_cache_limit,
+			execute_gas_limit_multiplier: self.execute_gas_limit_multiplier,
+		}
+	}
+}
+
+/// Opens the Frontier database and spawns the tasks keeping it and the RPC caches up to date.
+pub fn start_frontier(
+	config: &Configuration,
+	eth_config: &EthConfiguration,
+	client: Arc<FullClient>,
+	backend: Arc<FullBackend>,
+	task_manager: &TaskManager,
+) -> Result<Frontier, ServiceError> {
+	let frontier_backend = open_frontier_backend(client.clone(), config)?;
+	let overrides = fc_storage::overrides_handle(client.clone());
+	let filter_pool: FilterPool = Arc::new(Mutex::new(BTreeMap::new()));
+	let fee_history_cache: FeeHistoryCache = Arc::new(Mutex::new(BTreeMap::new()));
+	let fee_history_cache_limit = eth_config.fee_history_limit;
+
+	// Maps every imported block to its Ethereum block and transactions.
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-mapping-sync-worker",
+		Some("frontier"),
+		MappingSyncWorker::new(
+			client.import_notification_stream(),
+			Duration::new(6, 0),
+			client.clone(),
+			backend,
+			overrides.clone(),
+			frontier_backend.clone(),
+			3,
+			0,
+			SyncStrategy::Normal,
+		)
+		.for_each(|()| future::ready(())),
+	);
+
+	// Filters installed with `eth_newFilter` expire after this many blocks without being polled.
+	const FILTER_RETAIN_THRESHOLD: u64 = 100;
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-filter-pool",
+		Some("frontier"),
+		EthTask::filter_pool_task(client.clone(), filter_pool.clone(), FILTER_RETAIN_THRESHOLD),
+	);
+
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-fee-history",
+		Some("frontier"),
+		EthTask::fee_history_task(
+			client,
+			overrides.clone(),
+			fee_history_cache.clone(),
+			fee_history_cache_limit,
+		),
+	);
+
+	let block_data_cache = Arc::new(EthBlockDataCacheTask::new(
+		task_manager.spawn_handle(),
+		overrides.clone(),
+		eth_config.eth_log_block_cache,
+		eth_config.eth_statuses_cache,
+		config.prometheus_registry().cloned(),
+	));
+
+	Ok(Frontier {
+		backend: frontier_backend,
+		overrides,
+		block_data_cache,
+		filter_pool,
+		fee_history_cache,
+		fee_history_cache_limit,
+		max_past_logs: eth_config.max_past_logs,
+		execute_gas_limit_multiplier: eth_config.execute_gas_limit_multiplier,
+	})
+}
+
+/// Directory of the Frontier database, next to the databases of the chain.
+fn frontier_database_dir(config: &Configuration, path: &str) -> PathBuf {
+	config
+		.base_path
+		.as_ref()
+		.map(|base_path| base_path.config_dir(config.chain_spec.id()))
+		.unwrap_or_else(|| {
+			BasePath::from_project("", "", "lunes-node").config_dir(config.chain_spec.id())
+		})
+		.join("frontier")
+		.join(path)
+}
+
+fn open_frontier_backend(
+	client: Arc<FullClient>,
+	config: &Configuration,
+) -> Result<Arc<fc_db::Backend<Block>>, ServiceError> {
+	use sc_service::config::DatabaseSource;
+
+	let source = match config.database {
+		DatabaseSource::RocksDb { .. } =>
+			DatabaseSource::RocksDb { path: frontier_database_dir(config, "db"), cache_size: 0 },
+		DatabaseSource::ParityDb { .. } =>
+			DatabaseSource::ParityDb { path: frontier_database_dir(config, "paritydb") },
+		DatabaseSource::Auto { .. } => DatabaseSource::Auto {
+			rocksdb_path: frontier_database_dir(config, "db"),
+			paritydb_path: frontier_database_dir(config, "paritydb"),
+			cache_size: 0,
+		},
+		_ => return Err(ServiceError::Other("Supported databases: rocksdb, paritydb, auto".into())),
+	};
+
+	fc_db::Backend::<Block>::new(client, &fc_db::DatabaseSettings { source })
+		.map(Arc::new)
+		.map_err(ServiceError::Other)
+}
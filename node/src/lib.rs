@@ -1,3 +1,5 @@
 pub mod chain_spec;
+pub mod cli;
+pub mod eth;
 pub mod rpc;
 pub mod service;
\ No newline at end of file
@@ -5,12 +5,24 @@
 
 #![warn(missing_docs)]
 
-use std::sync::Arc;
+pub mod assets;
+pub mod contracts;
+pub mod eth;
+pub mod multisig;
+pub mod nfts;
+pub mod supply;
+pub mod vesting;
 
+use std::sync::Arc;
 
+use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
 use lunes_runtime::{ AccountId, Balance, Index};
-use node_primitives::Block;
+use node_primitives::{Block, BlockNumber, Hash};
+use sc_client_api::{backend::StorageProvider, client::BlockchainEvents, AuxStore, Backend};
+use sc_consensus_manual_seal::EngineCommand;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_transaction_pool::ChainApi;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -19,36 +31,74 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 pub use sc_rpc_api::DenyUnsafe;
 
 /// Full client dependencies.
-pub struct FullDeps<C, P> {
+pub struct FullDeps<C, P, A: ChainApi> {
 	/// The client instance to use.
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Channel to the manual-seal authorship task, when the node runs with `--sealing`.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
+	/// Dependencies of the Ethereum RPCs, when the node serves them (`--enable-eth-rpc`).
+	pub eth: Option<eth::EthDeps<C, P, A>>,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
-	deps: FullDeps<C, P>,
+pub fn create_full<C, P, BE, A>(
+	deps: FullDeps<C, P, A>,
+	subscription_task_executor: SubscriptionTaskExecutor,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+	C: StorageProvider<Block, BE> + BlockchainEvents<Block> + AuxStore,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: lunes_runtime::assets_api::AssetsApi<Block, AccountId, Balance, u32>,
+	C::Api: pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32>,
+	C::Api: lunes_runtime::nfts_api::NftsEnumerationApi<Block, AccountId, u32, u32>,
+	C::Api: pallet_contracts::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	C::Api: lunes_runtime::supply_api::SupplyApi<Block, Balance>,
+	C::Api: lunes_runtime::multisig_api::MultisigApi<Block, AccountId, Balance, BlockNumber>,
+	C::Api: lunes_runtime::vesting_api::VestingApi<Block, AccountId, Balance>,
+	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
+	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + 'static,
+	BE: Backend<Block> + 'static,
+	P: TransactionPool<Block = Block> + 'static,
+	A: ChainApi<Block = Block> + 'static,
 {
+	use assets::{Assets, AssetsApiServer};
+	use contracts::{Contracts, ContractsApiServer};
+	use multisig::{Multisig, MultisigApiServer};
+	use nfts::{Nfts, NftsApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use sc_consensus_manual_seal::rpc::{ManualSeal, ManualSealApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
+	use supply::{Supply, SupplyApiServer};
+	use vesting::{Vesting, VestingApiServer};
 
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, pool, deny_unsafe, command_sink, eth } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Assets::new(client.clone()).into_rpc())?;
+	module.merge(Nfts::new(client.clone()).into_rpc())?;
+	module.merge(Contracts::new(client.clone()).into_rpc())?;
+	module.merge(Supply::new(client.clone()).into_rpc())?;
+	module.merge(Multisig::new(client.clone()).into_rpc())?;
+	module.merge(Vesting::new(client).into_rpc())?;
+	if let Some(eth) = eth {
+		eth::extend_with_eth(&mut module, eth, subscription_task_executor)?;
+	}
+
+	if let Some(command_sink) = command_sink {
+		// `engine_createBlock` / `engine_finalizeBlock` forward to the manual-seal task.
+		module.merge(ManualSeal::new(command_sink).into_rpc())?;
+	}
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
 	// to call into the runtime.
@@ -0,0 +1,147 @@
+//! RPC interface for the `pallet_assets` holdings of an account.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use lunes_runtime::assets_api::AssetsApi as AssetsRuntimeApi;
+use node_primitives::Balance;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// An asset held by an account, along with the asset's metadata and total supply.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBalance<AssetId> {
+	/// Id of the asset.
+	pub asset_id: AssetId,
+	/// Amount of the asset held by the account.
+	pub balance: NumberOrHex,
+	/// Name of the asset, empty if no metadata is set.
+	pub name: String,
+	/// Ticker symbol of the asset, empty if no metadata is set.
+	pub symbol: String,
+	/// Number of decimals used to display balances.
+	pub decimals: u8,
+	/// Total amount of the asset in existence.
+	pub total_supply: NumberOrHex,
+}
+
+#[rpc(server)]
+pub trait AssetsApi<BlockHash, AccountId, AssetId> {
+	/// Returns every asset held by `account`, with its balance, metadata and total supply.
+	#[method(name = "assets_accountBalances")]
+	fn account_balances(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<AssetBalance<AssetId>>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The runtime at the queried block does not provide asset metadata.
+	UnsupportedAtBlock,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::UnsupportedAtBlock => 2,
+		}
+	}
+}
+
+/// Provides RPC methods to query the assets of an account.
+pub struct Assets<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Assets<C, Block> {
+	/// Creates a new instance of the Assets RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, AssetId> AssetsApiServer<<Block as BlockT>::Hash, AccountId, AssetId>
+	for Assets<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AssetsRuntimeApi<Block, AccountId, Balance, AssetId>,
+	AccountId: Codec + Send + Sync + 'static,
+	AssetId: Codec + Clone + Send + Sync + 'static,
+{
+	fn account_balances(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<AssetBalance<AssetId>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		// `asset_info` was added in version 2 of the runtime API.
+		let version = api
+			.api_version::<dyn AssetsRuntimeApi<Block, AccountId, Balance, AssetId>>(at)
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query the assets runtime API version.",
+					Some(e.to_string()),
+				))
+			})?;
+		if version.map_or(true, |version| version < 2) {
+			return Err(CallError::Custom(ErrorObject::owned(
+				Error::UnsupportedAtBlock.into(),
+				"Asset metadata is not supported at this block.",
+				None::<()>,
+			))
+			.into())
+		}
+
+		let balances = api.account_balances(at, account).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query account balances.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		balances
+			.into_iter()
+			.map(|(asset_id, balance)| {
+				let info = api
+					.asset_info(at, asset_id.clone())
+					.map_err(|e| {
+						CallError::Custom(ErrorObject::owned(
+							Error::RuntimeError.into(),
+							"Unable to query asset info.",
+							Some(e.to_string()),
+						))
+					})?
+					.unwrap_or_default();
+
+				Ok(AssetBalance {
+					asset_id,
+					balance: balance.into(),
+					name: String::from_utf8_lossy(&info.name).into_owned(),
+					symbol: String::from_utf8_lossy(&info.symbol).into_owned(),
+					decimals: info.decimals,
+					total_supply: info.supply.into(),
+				})
+			})
+			.collect()
+	}
+}
@@ -0,0 +1,387 @@
+//! RPC interface for dry-running `pallet_contracts` calls, instantiations and code uploads.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use node_primitives::{Balance, BlockNumber};
+use pallet_contracts::ContractsApi as ContractsRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiError, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{traits::Block as BlockT, DispatchError};
+use sp_weights::Weight;
+
+/// A contract call to dry-run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CallRequest<AccountId> {
+	/// Account the call is made from.
+	pub origin: AccountId,
+	/// Contract to call.
+	pub dest: AccountId,
+	/// Value transferred along with the call.
+	pub value: NumberOrHex,
+	/// Gas limit, the maximum block weight if omitted.
+	pub gas_limit: Option<Weight>,
+	/// Storage deposit limit, unlimited if omitted.
+	pub storage_deposit_limit: Option<NumberOrHex>,
+	/// SCALE encoded selector and arguments.
+	pub input_data: Bytes,
+}
+
+/// Code to instantiate a contract from.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Code<Hash> {
+	/// Wasm blob uploaded along with the instantiation.
+	Upload(Bytes),
+	/// Hash of code that is already on chain.
+	Existing(Hash),
+}
+
+/// A contract instantiation to dry-run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct InstantiateRequest<AccountId, Hash> {
+	/// Account the contract is instantiated from.
+	pub origin: AccountId,
+	/// Value transferred to the new contract.
+	pub value: NumberOrHex,
+	/// Gas limit, the maximum block weight if omitted.
+	pub gas_limit: Option<Weight>,
+	/// Storage deposit limit, unlimited if omitted.
+	pub storage_deposit_limit: Option<NumberOrHex>,
+	/// Code of the contract.
+	pub code: Code<Hash>,
+	/// SCALE encoded constructor selector and arguments.
+	pub data: Bytes,
+	/// Salt used to derive the contract address.
+	pub salt: Bytes,
+}
+
+/// Whether uploaded code may use instructions that are not deterministic.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Determinism {
+	/// The code must be deterministic, required for code that is called on-chain.
+	#[default]
+	Deterministic,
+	/// The code may only be executed off-chain.
+	AllowIndeterminism,
+}
+
+impl From<Determinism> for pallet_contracts::Determinism {
+	fn from(determinism: Determinism) -> Self {
+		match determinism {
+			Determinism::Deterministic => Self::Deterministic,
+			Determinism::AllowIndeterminism => Self::AllowIndeterminism,
+		}
+	}
+}
+
+/// A code upload to dry-run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CodeUploadRequest<AccountId> {
+	/// Account the code is uploaded from.
+	pub origin: AccountId,
+	/// Wasm blob.
+	pub code: Bytes,
+	/// Storage deposit limit, unlimited if omitted.
+	pub storage_deposit_limit: Option<NumberOrHex>,
+	/// Determinism requirement of the code, `deterministic` if omitted.
+	#[serde(default)]
+	pub determinism: Determinism,
+}
+
+/// Storage deposit charged to or refunded to the origin.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageDeposit {
+	/// Deposit refunded to the origin.
+	Refund(NumberOrHex),
+	/// Deposit charged from the origin.
+	Charge(NumberOrHex),
+}
+
+impl From<pallet_contracts_primitives::StorageDeposit<Balance>> for StorageDeposit {
+	fn from(deposit: pallet_contracts_primitives::StorageDeposit<Balance>) -> Self {
+		match deposit {
+			pallet_contracts_primitives::StorageDeposit::Refund(amount) =>
+				Self::Refund(amount.into()),
+			pallet_contracts_primitives::StorageDeposit::Charge(amount) =>
+				Self::Charge(amount.into()),
+		}
+	}
+}
+
+/// Outcome of a dry-run along with the resources it needs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractResult<R> {
+	/// Weight consumed by the dry-run.
+	pub gas_consumed: Weight,
+	/// Weight to pass as gas limit for the same call to succeed on chain.
+	pub gas_required: Weight,
+	/// Storage deposit the same call would charge or refund on chain.
+	pub storage_deposit: StorageDeposit,
+	/// Debug output of the contracts involved, lossily decoded as UTF-8.
+	pub debug_message: String,
+	/// Result of the execution.
+	pub result: Result<R, DispatchError>,
+}
+
+impl<R> ContractResult<R> {
+	fn from_runtime<T>(
+		result: pallet_contracts_primitives::ContractResult<Result<T, DispatchError>, Balance>,
+		map: impl FnOnce(T) -> R,
+	) -> Self {
+		Self {
+			gas_consumed: result.gas_consumed,
+			gas_required: result.gas_required,
+			storage_deposit: result.storage_deposit.into(),
+			debug_message: String::from_utf8_lossy(&result.debug_message).into_owned(),
+			result: result.result.map(map),
+		}
+	}
+}
+
+/// Data returned by a contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecReturnValue {
+	/// Whether the contract reverted its state changes.
+	pub reverted: bool,
+	/// SCALE encoded return value.
+	pub data: Bytes,
+}
+
+impl From<pallet_contracts_primitives::ExecReturnValue> for ExecReturnValue {
+	fn from(value: pallet_contracts_primitives::ExecReturnValue) -> Self {
+		Self { reverted: value.did_revert(), data: value.data.into() }
+	}
+}
+
+/// Data returned by a contract constructor.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateReturnValue<AccountId> {
+	/// Output of the constructor.
+	pub result: ExecReturnValue,
+	/// Address of the new contract.
+	pub account_id: AccountId,
+}
+
+/// Outcome of a successful code upload.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeUploadReturnValue<Hash> {
+	/// Hash of the uploaded code.
+	pub code_hash: Hash,
+	/// Storage deposit charged for the code.
+	pub deposit: NumberOrHex,
+}
+
+#[rpc(server)]
+pub trait ContractsApi<BlockHash, AccountId, Hash> {
+	/// Dry-runs a contract call.
+	#[method(name = "contracts_call")]
+	fn call(
+		&self,
+		call_request: CallRequest<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<ContractResult<ExecReturnValue>>;
+
+	/// Dry-runs a contract instantiation.
+	#[method(name = "contracts_instantiate")]
+	fn instantiate(
+		&self,
+		instantiate_request: InstantiateRequest<AccountId, Hash>,
+		at: Option<BlockHash>,
+	) -> RpcResult<ContractResult<InstantiateReturnValue<AccountId>>>;
+
+	/// Dry-runs a code upload.
+	#[method(name = "contracts_uploadCode")]
+	fn upload_code(
+		&self,
+		upload_request: CodeUploadRequest<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Result<CodeUploadReturnValue<Hash>, DispatchError>>;
+
+	/// Returns the value under `key` in the storage of the contract at `address`.
+	#[method(name = "contracts_getStorage")]
+	fn get_storage(
+		&self,
+		address: AccountId,
+		key: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Bytes>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// A balance in the request does not fit the runtime balance type.
+	InvalidBalance,
+	/// The contract storage could not be accessed.
+	ContractAccessError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidBalance => 2,
+			Error::ContractAccessError => 3,
+		}
+	}
+}
+
+fn runtime_error(e: ApiError) -> CallError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to dry-run contract.",
+		Some(e.to_string()),
+	))
+}
+
+fn decode_balance(value: NumberOrHex) -> Result<Balance, CallError> {
+	value.clone().try_into().map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			Error::InvalidBalance.into(),
+			"Balance out of range.",
+			Some(format!("{:?} does not fit into the balance type", value)),
+		))
+	})
+}
+
+/// Provides RPC methods to dry-run contracts.
+pub struct Contracts<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Contracts<C, Block> {
+	/// Creates a new instance of the Contracts RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, Hash> ContractsApiServer<<Block as BlockT>::Hash, AccountId, Hash>
+	for Contracts<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>,
+	AccountId: Codec + Send + Sync + 'static,
+	Hash: Codec + Send + Sync + 'static,
+{
+	fn call(
+		&self,
+		call_request: CallRequest<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<ContractResult<ExecReturnValue>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let CallRequest { origin, dest, value, gas_limit, storage_deposit_limit, input_data } =
+			call_request;
+
+		let value = decode_balance(value)?;
+		let storage_deposit_limit = storage_deposit_limit.map(decode_balance).transpose()?;
+
+		let result = self
+			.client
+			.runtime_api()
+			.call(at, origin, dest, value, gas_limit, storage_deposit_limit, input_data.to_vec())
+			.map_err(runtime_error)?;
+		Ok(ContractResult::from_runtime(result, Into::into))
+	}
+
+	fn instantiate(
+		&self,
+		instantiate_request: InstantiateRequest<AccountId, Hash>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<ContractResult<InstantiateReturnValue<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let InstantiateRequest { origin, value, gas_limit, storage_deposit_limit, code, data, salt } =
+			instantiate_request;
+
+		let value = decode_balance(value)?;
+		let storage_deposit_limit = storage_deposit_limit.map(decode_balance).transpose()?;
+		let code = match code {
+			Code::Upload(code) => pallet_contracts_primitives::Code::Upload(code.to_vec()),
+			Code::Existing(hash) => pallet_contracts_primitives::Code::Existing(hash),
+		};
+
+		let result = self
+			.client
+			.runtime_api()
+			.instantiate(
+				at,
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data.to_vec(),
+				salt.to_vec(),
+			)
+			.map_err(runtime_error)?;
+		Ok(ContractResult::from_runtime(result, |value| InstantiateReturnValue {
+			result: value.result.into(),
+			account_id: value.account_id,
+		}))
+	}
+
+	fn upload_code(
+		&self,
+		upload_request: CodeUploadRequest<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Result<CodeUploadReturnValue<Hash>, DispatchError>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let CodeUploadRequest { origin, code, storage_deposit_limit, determinism } =
+			upload_request;
+
+		let storage_deposit_limit = storage_deposit_limit.map(decode_balance).transpose()?;
+
+		let result = self
+			.client
+			.runtime_api()
+			.upload_code(at, origin, code.to_vec(), storage_deposit_limit, determinism.into())
+			.map_err(runtime_error)?;
+		Ok(result.map(|value| CodeUploadReturnValue {
+			code_hash: value.code_hash,
+			deposit: value.deposit.into(),
+		}))
+	}
+
+	fn get_storage(
+		&self,
+		address: AccountId,
+		key: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = self
+			.client
+			.runtime_api()
+			.get_storage(at, address, key.to_vec())
+			.map_err(runtime_error)?
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::ContractAccessError.into(),
+					"Unable to read contract storage.",
+					Some(format!("{:?}", e)),
+				))
+			})?;
+		Ok(value.map(Into::into))
+	}
+}
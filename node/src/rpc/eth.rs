@@ -0,0 +1,129 @@
+//! Ethereum compatible RPCs (`eth_*`, `net_*` and `web3_*`), served by Frontier.
+
+use std::sync::Arc;
+
+use fc_rpc::{
+	Eth, EthApiServer, EthBlockDataCacheTask, EthFilter, EthFilterApiServer, EthPubSub,
+	EthPubSubApiServer, Net, NetApiServer, OverrideHandle, Web3, Web3ApiServer,
+};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use fp_rpc::{ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi, NoTransactionConverter};
+use jsonrpsee::RpcModule;
+use node_primitives::{Block, Hash};
+use sc_client_api::{backend::StorageProvider, client::BlockchainEvents, AuxStore, Backend};
+use sc_network::NetworkService;
+use sc_network_sync::SyncingService;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_transaction_pool::{ChainApi, Pool};
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::ProvideRuntimeApi;
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+
+/// Dependencies of the Ethereum RPCs.
+pub struct EthDeps<C, P, A: ChainApi> {
+	/// The client instance to use.
+	pub client: Arc<C>,
+	/// Transaction pool instance.
+	pub pool: Arc<P>,
+	/// Graph of the transaction pool, used to look up pending transactions.
+	pub graph: Arc<Pool<A>>,
+	/// Whether the node authors blocks.
+	pub is_authority: bool,
+	/// Network service, for `net_*`.
+	pub network: Arc<NetworkService<Block, Hash>>,
+	/// Syncing service, for `eth_syncing`.
+	pub sync: Arc<SyncingService<Block>>,
+	/// Database mapping Ethereum blocks and transactions to LUNES blocks.
+	pub frontier_backend: Arc<fc_db::Backend<Block>>,
+	/// Reads the Ethereum data straight from the runtime storage.
+	pub overrides: Arc<OverrideHandle<Block>>,
+	/// Cache of Ethereum blocks and transaction statuses.
+	pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+	/// Filters installed with `eth_newFilter` and `eth_newBlockFilter`.
+	pub filter_pool: FilterPool,
+	/// Maximum number of logs returned by a single query.
+	pub max_past_logs: u32,
+	/// Cache of the `eth_feeHistory` data.
+	pub fee_history_cache: FeeHistoryCache,
+	/// Maximum number of blocks kept in the fee history cache.
+	pub fee_history_cache_limit: FeeHistoryCacheLimit,
+	/// `eth_call` and `eth_estimateGas` may use up to this many times the block gas limit.
+	pub execute_gas_limit_multiplier: u64,
+}
+
+/// Maximum number of filters installed at once.
+const MAX_STORED_FILTERS: usize = 500;
+
+/// Adds the Ethereum RPCs to `module`.
+pub fn extend_with_eth<C, BE, P, A>(
+	module: &mut RpcModule<()>,
+	deps: EthDeps<C, P, A>,
+	subscription_task_executor: SubscriptionTaskExecutor,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+where
+	C: ProvideRuntimeApi<Block> + StorageProvider<Block, BE> + AuxStore,
+	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: BlockchainEvents<Block> + Send + Sync + 'static,
+	C::Api: BlockBuilder<Block>,
+	C::Api: EthereumRuntimeRPCApi<Block> + ConvertTransactionRuntimeApi<Block>,
+	BE: Backend<Block> + 'static,
+	P: TransactionPool<Block = Block> + 'static,
+	A: ChainApi<Block = Block> + 'static,
+{
+	let EthDeps {
+		client,
+		pool,
+		graph,
+		is_authority,
+		network,
+		sync,
+		frontier_backend,
+		overrides,
+		block_data_cache,
+		filter_pool,
+		max_past_logs,
+		fee_history_cache,
+		fee_history_cache_limit,
+		execute_gas_limit_multiplier,
+	} = deps;
+
+	module.merge(
+		Eth::new(
+			client.clone(),
+			pool.clone(),
+			graph,
+			// Raw Ethereum transactions are wrapped by the runtime itself.
+			None::<NoTransactionConverter>,
+			sync.clone(),
+			// LUNES keeps no Ethereum keys on the node.
+			Vec::new(),
+			overrides.clone(),
+			frontier_backend.clone(),
+			is_authority,
+			block_data_cache.clone(),
+			fee_history_cache,
+			fee_history_cache_limit,
+			execute_gas_limit_multiplier,
+		)
+		.into_rpc(),
+	)?;
+	module.merge(
+		EthFilter::new(
+			client.clone(),
+			frontier_backend,
+			filter_pool,
+			MAX_STORED_FILTERS,
+			max_past_logs,
+			block_data_cache,
+		)
+		.into_rpc(),
+	)?;
+	module.merge(Net::new(client.clone(), network, true).into_rpc())?;
+	module.merge(Web3::new(client.clone()).into_rpc())?;
+	module.merge(
+		EthPubSub::new(pool, client, sync, subscription_task_executor, overrides).into_rpc(),
+	)?;
+
+	Ok(())
+}
@@ -0,0 +1,133 @@
+//! RPC interface for pending multisig operations.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use lunes_runtime::multisig_api::MultisigApi as MultisigRuntimeApi;
+use node_primitives::{Balance, BlockNumber};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// A multisig operation that has not collected enough approvals yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingOperation<AccountId> {
+	/// Hash of the call dispatched once the threshold is reached.
+	pub call_hash: H256,
+	/// Block in which the operation was opened.
+	pub height: BlockNumber,
+	/// Index of the opening extrinsic in that block.
+	pub index: u32,
+	/// The signatory that opened the operation and holds its deposit.
+	pub depositor: AccountId,
+	/// Deposit reserved from the depositor.
+	pub deposit: NumberOrHex,
+	/// Signatories that approved the operation so far.
+	pub approvals: Vec<AccountId>,
+}
+
+#[rpc(server)]
+pub trait MultisigApi<BlockHash, AccountId> {
+	/// Returns the pending operations of the multisig account `multisig`, oldest first.
+	#[method(name = "multisig_pendingOperations")]
+	fn pending_operations(
+		&self,
+		multisig: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<PendingOperation<AccountId>>>;
+
+	/// Returns the multisig account of `signatories` for `threshold`.
+	#[method(name = "multisig_account")]
+	fn account(
+		&self,
+		signatories: Vec<AccountId>,
+		threshold: u16,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountId>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: sp_api::ApiError) -> CallError {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+}
+
+/// Provides RPC methods to query multisig operations.
+pub struct Multisig<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Multisig<C, Block> {
+	/// Creates a new instance of the Multisig RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> MultisigApiServer<<Block as BlockT>::Hash, AccountId>
+	for Multisig<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: MultisigRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	fn pending_operations(
+		&self,
+		multisig: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<PendingOperation<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let operations = api
+			.pending_operations(at, multisig)
+			.map_err(|e| runtime_error("Unable to query the pending multisig operations.", e))?;
+		Ok(operations
+			.into_iter()
+			.map(|operation| PendingOperation {
+				call_hash: operation.call_hash,
+				height: operation.height,
+				index: operation.index,
+				depositor: operation.depositor,
+				deposit: operation.deposit.into(),
+				approvals: operation.approvals,
+			})
+			.collect())
+	}
+
+	fn account(
+		&self,
+		signatories: Vec<AccountId>,
+		threshold: u16,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<AccountId> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.multisig_account(at, signatories, threshold)
+			.map_err(|e| runtime_error("Unable to derive the multisig account.", e).into())
+	}
+}
@@ -0,0 +1,281 @@
+//! RPC interface for querying `pallet_nfts` collections, items and attributes.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use lunes_runtime::nfts_api::NftsEnumerationApi;
+use pallet_nfts_runtime_api::NftsApi as NftsRuntimeApi;
+use sp_api::{ApiError, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+/// Upper bound on the `limit` of the paginated queries.
+pub const MAX_PAGE_SIZE: u32 = 1_000;
+
+#[rpc(server)]
+pub trait NftsApi<BlockHash, AccountId, CollectionId, ItemId> {
+	/// Returns the owner of `item` in `collection`.
+	#[method(name = "nfts_owner")]
+	fn owner(
+		&self,
+		collection: CollectionId,
+		item: ItemId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AccountId>>;
+
+	/// Returns the owner of `collection`.
+	#[method(name = "nfts_collectionOwner")]
+	fn collection_owner(
+		&self,
+		collection: CollectionId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AccountId>>;
+
+	/// Returns the attribute `key` of `item`, as set by the collection owner.
+	#[method(name = "nfts_attribute")]
+	fn attribute(
+		&self,
+		collection: CollectionId,
+		item: ItemId,
+		key: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Bytes>>;
+
+	/// Returns the attribute `key` of `item`, as set by `account`.
+	#[method(name = "nfts_customAttribute")]
+	fn custom_attribute(
+		&self,
+		account: AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		key: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Bytes>>;
+
+	/// Returns the attribute `key` of `item`, as set by the pallet itself.
+	#[method(name = "nfts_systemAttribute")]
+	fn system_attribute(
+		&self,
+		collection: CollectionId,
+		item: ItemId,
+		key: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Bytes>>;
+
+	/// Returns the attribute `key` of `collection`.
+	#[method(name = "nfts_collectionAttribute")]
+	fn collection_attribute(
+		&self,
+		collection: CollectionId,
+		key: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<Bytes>>;
+
+	/// Returns up to `limit` `(collection, item)` pairs owned by `account`.
+	///
+	/// Pages start after `start_key`, the last pair of the previous page, or from the first pair
+	/// if it is `null`.
+	#[method(name = "nfts_ownedItems")]
+	fn owned_items(
+		&self,
+		account: AccountId,
+		start_key: Option<(CollectionId, ItemId)>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<(CollectionId, ItemId)>>;
+
+	/// Returns up to `limit` items of `collection`.
+	///
+	/// Pages start after `start_key`, the last item of the previous page, or from the first item
+	/// if it is `null`.
+	#[method(name = "nfts_collectionItems")]
+	fn collection_items(
+		&self,
+		collection: CollectionId,
+		start_key: Option<ItemId>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ItemId>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The requested page is larger than [`MAX_PAGE_SIZE`].
+	PageTooLarge,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::PageTooLarge => 2,
+		}
+	}
+}
+
+fn runtime_error(e: ApiError) -> CallError {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query NFTs.",
+		Some(e.to_string()),
+	))
+}
+
+fn ensure_page_size(limit: u32) -> Result<(), CallError> {
+	if limit > MAX_PAGE_SIZE {
+		return Err(CallError::Custom(ErrorObject::owned(
+			Error::PageTooLarge.into(),
+			"Requested page is too large.",
+			Some(format!("limit {} exceeds the maximum of {}", limit, MAX_PAGE_SIZE)),
+		)))
+	}
+	Ok(())
+}
+
+/// Provides RPC methods to query NFTs.
+pub struct Nfts<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Nfts<C, Block> {
+	/// Creates a new instance of the Nfts RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, CollectionId, ItemId>
+	NftsApiServer<<Block as BlockT>::Hash, AccountId, CollectionId, ItemId> for Nfts<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: NftsRuntimeApi<Block, AccountId, CollectionId, ItemId>,
+	C::Api: NftsEnumerationApi<Block, AccountId, CollectionId, ItemId>,
+	AccountId: Codec + Send + Sync + 'static,
+	CollectionId: Codec + Send + Sync + 'static,
+	ItemId: Codec + Send + Sync + 'static,
+{
+	fn owner(
+		&self,
+		collection: CollectionId,
+		item: ItemId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		Ok(self.client.runtime_api().owner(at, collection, item).map_err(runtime_error)?)
+	}
+
+	fn collection_owner(
+		&self,
+		collection: CollectionId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		Ok(self.client.runtime_api().collection_owner(at, collection).map_err(runtime_error)?)
+	}
+
+	fn attribute(
+		&self,
+		collection: CollectionId,
+		item: ItemId,
+		key: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = self
+			.client
+			.runtime_api()
+			.attribute(at, collection, item, key.to_vec())
+			.map_err(runtime_error)?;
+		Ok(value.map(Into::into))
+	}
+
+	fn custom_attribute(
+		&self,
+		account: AccountId,
+		collection: CollectionId,
+		item: ItemId,
+		key: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = self
+			.client
+			.runtime_api()
+			.custom_attribute(at, account, collection, item, key.to_vec())
+			.map_err(runtime_error)?;
+		Ok(value.map(Into::into))
+	}
+
+	fn system_attribute(
+		&self,
+		collection: CollectionId,
+		item: ItemId,
+		key: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = self
+			.client
+			.runtime_api()
+			.system_attribute(at, collection, item, key.to_vec())
+			.map_err(runtime_error)?;
+		Ok(value.map(Into::into))
+	}
+
+	fn collection_attribute(
+		&self,
+		collection: CollectionId,
+		key: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<Bytes>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let value = self
+			.client
+			.runtime_api()
+			.collection_attribute(at, collection, key.to_vec())
+			.map_err(runtime_error)?;
+		Ok(value.map(Into::into))
+	}
+
+	fn owned_items(
+		&self,
+		account: AccountId,
+		start_key: Option<(CollectionId, ItemId)>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<(CollectionId, ItemId)>> {
+		ensure_page_size(limit)?;
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		Ok(self
+			.client
+			.runtime_api()
+			.owned_items(at, account, start_key, limit)
+			.map_err(runtime_error)?)
+	}
+
+	fn collection_items(
+		&self,
+		collection: CollectionId,
+		start_key: Option<ItemId>,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<ItemId>> {
+		ensure_page_size(limit)?;
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		Ok(self
+			.client
+			.runtime_api()
+			.collection_items(at, collection, start_key, limit)
+			.map_err(runtime_error)?)
+	}
+}
@@ -0,0 +1,124 @@
+//! RPC interface for the LUNES supply.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use lunes_runtime::supply_api::SupplyApi as SupplyRuntimeApi;
+use node_primitives::Balance;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// Breakdown of the LUNES supply.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupplyInfo {
+	/// Total amount of LUNES in existence.
+	pub total_issuance: NumberOrHex,
+	/// Balance of the treasury pot.
+	pub treasury: NumberOrHex,
+	/// Total amount burned so far, already excluded from the total issuance.
+	pub total_burned: NumberOrHex,
+	/// The total issuance minus the treasury pot.
+	pub circulating: NumberOrHex,
+}
+
+/// Amount burned in an era.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraBurn {
+	/// Index of the era.
+	pub era: u32,
+	/// Amount burned in the era.
+	pub burned: NumberOrHex,
+}
+
+#[rpc(server)]
+pub trait SupplyApi<BlockHash> {
+	/// Returns the total issuance, treasury pot, burned amount and circulating supply.
+	#[method(name = "supply_info")]
+	fn info(&self, at: Option<BlockHash>) -> RpcResult<SupplyInfo>;
+
+	/// Returns the circulating supply.
+	#[method(name = "supply_circulating")]
+	fn circulating(&self, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+
+	/// Returns the amount burned per era for the most recent eras, oldest first.
+	#[method(name = "supply_burnHistory")]
+	fn burn_history(&self, at: Option<BlockHash>) -> RpcResult<Vec<EraBurn>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: sp_api::ApiError) -> CallError {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+}
+
+/// Provides RPC methods to query the LUNES supply.
+pub struct Supply<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Supply<C, Block> {
+	/// Creates a new instance of the Supply RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> SupplyApiServer<<Block as BlockT>::Hash> for Supply<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: SupplyRuntimeApi<Block, Balance>,
+{
+	fn info(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<SupplyInfo> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let supply =
+			api.supply(at).map_err(|e| runtime_error("Unable to query the supply.", e))?;
+		Ok(SupplyInfo {
+			total_issuance: supply.total_issuance.into(),
+			treasury: supply.treasury.into(),
+			total_burned: supply.total_burned.into(),
+			circulating: supply.circulating.into(),
+		})
+	}
+
+	fn circulating(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<NumberOrHex> {
+		self.info(at).map(|info| info.circulating)
+	}
+
+	fn burn_history(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<EraBurn>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let history = api
+			.burn_history(at)
+			.map_err(|e| runtime_error("Unable to query the burn history.", e))?;
+		Ok(history
+			.into_iter()
+			.map(|(era, burned)| EraBurn { era, burned: burned.into() })
+			.collect())
+	}
+}
@@ -0,0 +1,93 @@
+//! RPC interface for vesting schedules.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use lunes_runtime::vesting_api::VestingApi as VestingRuntimeApi;
+use node_primitives::Balance;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// How much of the vesting schedules of an account has vested.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingBalance {
+	/// Total amount of the account's vesting schedules.
+	pub total: NumberOrHex,
+	/// Part of `total` that is still locked.
+	pub locked: NumberOrHex,
+	/// Part of `total` that has vested.
+	pub vested: NumberOrHex,
+}
+
+#[rpc(server)]
+pub trait VestingApi<BlockHash, AccountId> {
+	/// Returns the locked and vested amounts of `who`, or `null` if it has no vesting schedule.
+	#[method(name = "vesting_balance")]
+	fn balance(&self, who: AccountId, at: Option<BlockHash>) -> RpcResult<Option<VestingBalance>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: sp_api::ApiError) -> CallError {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+}
+
+/// Provides RPC methods to query vesting schedules.
+pub struct Vesting<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Vesting<C, Block> {
+	/// Creates a new instance of the Vesting RPC helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> VestingApiServer<<Block as BlockT>::Hash, AccountId> for Vesting<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: VestingRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec + Send + Sync + 'static,
+{
+	fn balance(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<VestingBalance>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let balance = api
+			.vesting_balance(at, who)
+			.map_err(|e| runtime_error("Unable to query the vesting balance.", e))?;
+		Ok(balance.map(|balance| VestingBalance {
+			total: balance.total.into(),
+			locked: balance.locked.into(),
+			vested: balance.vested.into(),
+		}))
+	}
+}
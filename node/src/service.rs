@@ -1,16 +1,34 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
-use std::{sync::Arc, time::Duration};
-
+use std::{
+	pin::Pin,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
+	time::Duration,
+};
+
+use futures::{channel::mpsc, stream, Stream, StreamExt};
+use futures_timer::Delay;
 use sc_client_api::BlockBackend;
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_grandpa::SharedVoterState;
+use sc_consensus_manual_seal::{
+	consensus::aura::AuraConsensusDataProvider, EngineCommand, ManualSealParams,
+};
 pub use sc_executor::NativeElseWasmExecutor;
-use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncParams};
+use sc_service::{
+	error::Error as ServiceError, Configuration, KeystoreContainer, TaskManager, WarpSyncParams,
+};
 use sc_telemetry::{Telemetry, TelemetryWorker};
+use sc_transaction_pool_api::TransactionPool;
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
 use sc_keystore::LocalKeystore;
+use sc_network::{Event, NetworkEventStream};
 use lunes_runtime::{self, RuntimeApi};
-use node_primitives::Block;
+use node_primitives::{Block, Hash};
+
+use crate::{cli::Sealing, eth::EthConfiguration};
 // Our native executor instance.
 pub struct ExecutorDispatch;
 
@@ -33,31 +51,22 @@ impl sc_executor::NativeExecutionDispatch for ExecutorDispatch {
 
 pub(crate) type FullClient =
 	sc_service::TFullClient<Block, RuntimeApi, NativeElseWasmExecutor<ExecutorDispatch>>;
-type FullBackend = sc_service::TFullBackend<Block>;
+pub(crate) type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
-
-pub fn new_partial(
-	config: &Configuration,
-) -> Result<
-	sc_service::PartialComponents<
-		FullClient,
-		FullBackend,
-		FullSelectChain,
-		sc_consensus::DefaultImportQueue<Block, FullClient>,
-		sc_transaction_pool::FullPool<Block, FullClient>,
-		(
-			sc_consensus_grandpa::GrandpaBlockImport<
-				FullBackend,
-				Block,
-				FullClient,
-				FullSelectChain,
-			>,
-			sc_consensus_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
-			Option<Telemetry>,
-		),
-	>,
-	ServiceError,
-> {
+pub(crate) type FullPool = sc_transaction_pool::FullPool<Block, FullClient>;
+
+/// The parts of a full node that do not depend on the consensus engine in use.
+type ChainComponents = (
+	Arc<FullClient>,
+	Arc<FullBackend>,
+	KeystoreContainer,
+	TaskManager,
+	FullSelectChain,
+	Arc<FullPool>,
+	Option<Telemetry>,
+);
+
+fn new_chain_components(config: &Configuration) -> Result<ChainComponents, ServiceError> {
 	if config.keystore_remote.is_some() {
 		return Err(ServiceError::Other("Remote Keystores are not supported.".into()))
 	}
@@ -103,6 +112,34 @@ pub fn new_partial(
 		client.clone(),
 	);
 
+	Ok((client, backend, keystore_container, task_manager, select_chain, transaction_pool, telemetry))
+}
+
+pub fn new_partial(
+	config: &Configuration,
+) -> Result<
+	sc_service::PartialComponents<
+		FullClient,
+		FullBackend,
+		FullSelectChain,
+		sc_consensus::DefaultImportQueue<Block, FullClient>,
+		FullPool,
+		(
+			sc_consensus_grandpa::GrandpaBlockImport<
+				FullBackend,
+				Block,
+				FullClient,
+				FullSelectChain,
+			>,
+			sc_consensus_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
+			Option<Telemetry>,
+		),
+	>,
+	ServiceError,
+> {
+	let (client, backend, keystore_container, task_manager, select_chain, transaction_pool, telemetry) =
+		new_chain_components(config)?;
+
 	let (grandpa_block_import, grandpa_link) = sc_consensus_grandpa::block_import(
 		client.clone(),
 		&(client.clone() as Arc<_>),
@@ -147,6 +184,41 @@ pub fn new_partial(
 	})
 }
 
+/// Like [`new_partial`], but importing blocks sealed by [`new_manual_seal`] instead of Aura.
+pub fn new_manual_seal_partial(
+	config: &Configuration,
+) -> Result<
+	sc_service::PartialComponents<
+		FullClient,
+		FullBackend,
+		FullSelectChain,
+		sc_consensus::DefaultImportQueue<Block, FullClient>,
+		FullPool,
+		Option<Telemetry>,
+	>,
+	ServiceError,
+> {
+	let (client, backend, keystore_container, task_manager, select_chain, transaction_pool, telemetry) =
+		new_chain_components(config)?;
+
+	let import_queue = sc_consensus_manual_seal::import_queue(
+		Box::new(client.clone()),
+		&task_manager.spawn_essential_handle(),
+		config.prometheus_registry(),
+	);
+
+	Ok(sc_service::PartialComponents {
+		client,
+		backend,
+		task_manager,
+		import_queue,
+		keystore_container,
+		select_chain,
+		transaction_pool,
+		other: telemetry,
+	})
+}
+
 fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 	// FIXME: here would the concrete keystore be built,
 	//        must return a concrete type (NOT `LocalKeystore`) that
@@ -155,7 +227,10 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+pub fn new_full(
+	mut config: Configuration,
+	eth_config: &EthConfiguration,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -203,6 +278,8 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 			warp_sync_params: Some(WarpSyncParams::WithProvider(warp_sync)),
 		})?;
 
+	// Validators send their im-online heartbeats from the offchain worker, signed with the key in
+	// the node's keystore.
 	if config.offchain_worker.enabled {
 		sc_service::build_offchain_workers(
 			&config,
@@ -219,14 +296,69 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	if role.is_authority() {
+		// Publishes the addresses of this validator under its authority discovery key, so the
+		// other validators can connect to it directly, and looks up theirs.
+		let authority_discovery_role =
+			sc_authority_discovery::Role::PublishAndDiscover(keystore_container.keystore());
+		let dht_event_stream =
+			network.event_stream("authority-discovery").filter_map(|e| async move {
+				match e {
+					Event::Dht(e) => Some(e),
+					_ => None,
+				}
+			});
+		let (authority_discovery_worker, _service) = sc_authority_discovery::new_worker_and_service(
+			client.clone(),
+			network.clone(),
+			Box::pin(dht_event_stream),
+			authority_discovery_role,
+			prometheus_registry.clone(),
+		);
+
+		task_manager.spawn_handle().spawn(
+			"authority-discovery-worker",
+			Some("networking"),
+			authority_discovery_worker.run(),
+		);
+	}
+
+	let frontier = if eth_config.enable_eth_rpc {
+		Some(crate::eth::start_frontier(
+			&config,
+			eth_config,
+			client.clone(),
+			backend.clone(),
+			&task_manager,
+		)?)
+	} else {
+		None
+	};
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
-
-		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
-			crate::rpc::create_full(deps).map_err(Into::into)
+		let network = network.clone();
+		let sync_service = sync_service.clone();
+		let is_authority = role.is_authority();
+
+		Box::new(move |deny_unsafe, subscription_task_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: None,
+				eth: frontier.as_ref().map(|frontier| {
+					frontier.rpc_deps(
+						client.clone(),
+						pool.clone(),
+						network.clone(),
+						sync_service.clone(),
+						is_authority,
+					)
+				}),
+			};
+			crate::rpc::create_full(deps, subscription_task_executor).map_err(Into::into)
 		})
 	};
 
@@ -339,4 +471,158 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 
 	network_starter.start_network();
 	Ok(task_manager)
-}
\ No newline at end of file
+}
+
+/// Builds a development service that seals blocks on demand instead of running Aura + GRANDPA.
+///
+/// Blocks still carry an Aura pre-digest so the runtime accepts them, and every sealing mode
+/// keeps the `engine_*` RPCs available for sealing and finalizing blocks explicitly.
+pub fn new_manual_seal(
+	config: Configuration,
+	sealing: Sealing,
+	eth_config: &EthConfiguration,
+) -> Result<TaskManager, ServiceError> {
+	let sc_service::PartialComponents {
+		client,
+		backend,
+		mut task_manager,
+		import_queue,
+		keystore_container,
+		select_chain,
+		transaction_pool,
+		other: mut telemetry,
+	} = new_manual_seal_partial(&config)?;
+
+	let (network, system_rpc_tx, tx_handler_controller, network_starter, sync_service) =
+		sc_service::build_network(sc_service::BuildNetworkParams {
+			config: &config,
+			client: client.clone(),
+			transaction_pool: transaction_pool.clone(),
+			spawn_handle: task_manager.spawn_handle(),
+			import_queue,
+			block_announce_validator_builder: None,
+			warp_sync_params: None,
+		})?;
+
+	if config.offchain_worker.enabled {
+		sc_service::build_offchain_workers(
+			&config,
+			task_manager.spawn_handle(),
+			client.clone(),
+			network.clone(),
+		);
+	}
+
+	let prometheus_registry = config.prometheus_registry().cloned();
+	let (command_sink, rpc_commands) = mpsc::channel(1024);
+
+	let frontier = if eth_config.enable_eth_rpc {
+		Some(crate::eth::start_frontier(
+			&config,
+			eth_config,
+			client.clone(),
+			backend.clone(),
+			&task_manager,
+		)?)
+	} else {
+		None
+	};
+
+	let rpc_extensions_builder = {
+		let client = client.clone();
+		let pool = transaction_pool.clone();
+		let network = network.clone();
+		let sync_service = sync_service.clone();
+
+		Box::new(move |deny_unsafe, subscription_task_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				command_sink: Some(command_sink.clone()),
+				// The development node seals the blocks itself.
+				eth: frontier.as_ref().map(|frontier| {
+					frontier.rpc_deps(
+						client.clone(),
+						pool.clone(),
+						network.clone(),
+						sync_service.clone(),
+						true,
+					)
+				}),
+			};
+			crate::rpc::create_full(deps, subscription_task_executor).map_err(Into::into)
+		})
+	};
+
+	let _rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
+		network,
+		client: client.clone(),
+		keystore: keystore_container.sync_keystore(),
+		task_manager: &mut task_manager,
+		transaction_pool: transaction_pool.clone(),
+		rpc_builder: rpc_extensions_builder,
+		backend,
+		system_rpc_tx,
+		tx_handler_controller,
+		sync_service,
+		config,
+		telemetry: telemetry.as_mut(),
+	})?;
+
+	let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+		task_manager.spawn_handle(),
+		client.clone(),
+		transaction_pool.clone(),
+		prometheus_registry.as_ref(),
+		telemetry.as_ref().map(|x| x.handle()),
+	);
+
+	let commands_stream: Pin<Box<dyn Stream<Item = EngineCommand<Hash>> + Send>> = match sealing {
+		Sealing::Manual => Box::pin(rpc_commands),
+		Sealing::Instant => Box::pin(stream::select(
+			rpc_commands,
+			transaction_pool.import_notification_stream().map(|_| seal_new_block(false)),
+		)),
+		Sealing::Interval(millis) => Box::pin(stream::select(
+			rpc_commands,
+			stream::unfold((), move |()| async move {
+				Delay::new(Duration::from_millis(millis)).await;
+				Some((seal_new_block(true), ()))
+			}),
+		)),
+	};
+
+	// Aura rejects a block whose slot does not increase, so instead of following the wall clock
+	// the timestamp advances by exactly one slot for every sealed block.
+	let slot_duration = sc_consensus_aura::slot_duration(&*client)?.as_millis();
+	let next_timestamp = Arc::new(AtomicU64::new(*sp_timestamp::Timestamp::current()));
+
+	let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+		block_import: client.clone(),
+		env: proposer_factory,
+		client: client.clone(),
+		pool: transaction_pool,
+		commands_stream,
+		select_chain,
+		consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+		create_inherent_data_providers: move |_, ()| {
+			let timestamp = next_timestamp.fetch_add(slot_duration, Ordering::SeqCst);
+			async move { Ok(sp_timestamp::InherentDataProvider::new(timestamp.into())) }
+		},
+	});
+
+	// the manual-seal authoring task is considered essential, i.e. if it
+	// fails we take down the service with it.
+	task_manager
+		.spawn_essential_handle()
+		.spawn_blocking("manual-seal", Some("block-authoring"), manual_seal);
+
+	network_starter.start_network();
+	Ok(task_manager)
+}
+
+/// Seal command issued by the `instant` and interval sealing modes.
+fn seal_new_block(create_empty: bool) -> EngineCommand<Hash> {
+	EngineCommand::SealNewBlock { create_empty, finalize: true, parent_hash: None, sender: None }
+}
@@ -0,0 +1,99 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Burns the imbalances it receives and keeps account of the burned amount.
+//!
+//! The pallet implements [`OnUnbalanced`], so it can be plugged in wherever the runtime used to
+//! drop an imbalance to burn it. Besides the all-time total, the amounts burned in the last
+//! [`Config::HistoryDepth`] eras are kept.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use sp_runtime::traits::Saturating;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Counter for the number of eras that have passed.
+pub type EraIndex = u32;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency that is burned.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The era burns are attributed to.
+		type CurrentEra: Get<EraIndex>;
+
+		/// Number of eras for which the burned amount is kept.
+		#[pallet::constant]
+		type HistoryDepth: Get<u32>;
+	}
+
+	/// Total amount burned through this pallet.
+	#[pallet::storage]
+	#[pallet::getter(fn total_burned)]
+	pub type TotalBurned<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Amount burned per era, oldest first, for the last `HistoryDepth` eras with burns.
+	#[pallet::storage]
+	#[pallet::getter(fn burn_history)]
+	pub type BurnHistory<T: Config> =
+		StorageValue<_, BoundedVec<(EraIndex, BalanceOf<T>), T::HistoryDepth>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Fees were burned in `era`.
+		FeesBurned { era: EraIndex, amount: BalanceOf<T> },
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn note_burned(era: EraIndex, amount: BalanceOf<T>) {
+		TotalBurned::<T>::mutate(|total| total.saturating_accrue(amount));
+		BurnHistory::<T>::mutate(|history| {
+			match history.last_mut() {
+				Some((last, burned)) if *last == era => burned.saturating_accrue(amount),
+				_ => {
+					if history.is_full() && !history.is_empty() {
+						history.remove(0);
+					}
+					// Cannot fail: there is room left after dropping the oldest era.
+					let _ = history.try_push((era, amount));
+				},
+			}
+		});
+	}
+}
+
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		let era = T::CurrentEra::get();
+		let burned = amount.peek();
+		// Dropping the imbalance reduces the total issuance.
+		drop(amount);
+
+		Self::note_burned(era, burned);
+		Self::deposit_event(Event::FeesBurned { era, amount: burned });
+	}
+}
@@ -0,0 +1,52 @@
+use crate::{mock::*, Event};
+use frame_support::traits::{Currency, OnUnbalanced};
+
+fn burn(era: u32, amount: u128) {
+	CurrentEra::set(era);
+	Burn::on_unbalanced(Balances::issue(amount));
+}
+
+#[test]
+fn burning_reduces_issuance_and_is_recorded() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let _ = Balances::deposit_creating(&1, 1_000);
+
+		burn(0, 100);
+		assert_eq!(Balances::total_issuance(), 1_000);
+		assert_eq!(Burn::total_burned(), 100);
+		assert_eq!(Burn::burn_history().into_inner(), vec![(0, 100)]);
+		System::assert_last_event(Event::FeesBurned { era: 0, amount: 100 }.into());
+	});
+}
+
+#[test]
+fn burns_are_grouped_by_era() {
+	new_test_ext().execute_with(|| {
+		burn(0, 10);
+		burn(0, 5);
+		burn(2, 7);
+		assert_eq!(Burn::total_burned(), 22);
+		assert_eq!(Burn::burn_history().into_inner(), vec![(0, 15), (2, 7)]);
+	});
+}
+
+#[test]
+fn history_is_bounded() {
+	new_test_ext().execute_with(|| {
+		for era in 0..5 {
+			burn(era, 1);
+		}
+		assert_eq!(Burn::total_burned(), 5);
+		assert_eq!(Burn::burn_history().into_inner(), vec![(2, 1), (3, 1), (4, 1)]);
+	});
+}
+
+#[test]
+fn zero_burn_is_ignored() {
+	new_test_ext().execute_with(|| {
+		burn(0, 0);
+		assert_eq!(Burn::total_burned(), 0);
+		assert!(Burn::burn_history().is_empty());
+	});
+}
@@ -0,0 +1,55 @@
+//! Benchmarking setup for pallet-evm-accounts
+
+use super::*;
+
+use crate::Pallet as EvmAccounts;
+use frame_benchmarking::v1::{benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::traits::Convert;
+
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"evma");
+
+/// Gives the fallback account of `address` funds and a nonce, so that claiming it moves both.
+fn fund_fallback<T: Config>(address: H160) {
+	let fallback = T::FallbackMapping::into_account_id(address);
+	T::Currency::make_free_balance_be(&fallback, T::Currency::minimum_balance() * 100u32.into());
+	frame_system::Pallet::<T>::inc_account_nonce(&fallback);
+}
+
+benchmarks! {
+	claim_address {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		let message = EvmAccounts::<T>::claim_message(&caller);
+		let mut signed = b"\x19Ethereum Signed Message:\n".to_vec();
+		signed.extend(decimal(message.len()));
+		signed.extend(message);
+		let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &keccak_256(&signed))
+			.expect("the key was just generated");
+		let mut signature_bytes = [0; 65];
+		signature_bytes.copy_from_slice(signature.as_ref());
+		let address = EvmAccounts::<T>::signer(
+			&EvmAccounts::<T>::claim_message(&caller),
+			&signature_bytes,
+		)
+		.expect("the signature is valid");
+		fund_fallback::<T>(address);
+	}: _(RawOrigin::Signed(caller.clone()), address, signature_bytes)
+	verify {
+		assert_eq!(Addresses::<T>::get(&caller), Some(address));
+	}
+
+	claim_default_address {
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		let address = T::DefaultAddress::convert(caller.clone());
+		fund_fallback::<T>(address);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(Addresses::<T>::get(&caller), Some(address));
+	}
+
+	impl_benchmark_test_suite!(EvmAccounts, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,214 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Links Ethereum addresses and LUNES accounts both ways.
+//!
+//! An account claims an address once, either by signing a message with the Ethereum key of the
+//! address or by taking the default address the runtime derives from the account. From then on
+//! the address acts as the account in the EVM, and the account can look up its address.
+//!
+//! Addresses nobody has claimed act as the account given by [`Config::FallbackMapping`], so they
+//! can receive and spend funds before they are claimed. Claiming an address moves those funds to
+//! the claiming account.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+use codec::Encode;
+use frame_support::{
+	ensure,
+	traits::{Currency, ExistenceRequirement},
+};
+use frame_system::RawOrigin;
+use pallet_evm::{AddressMapping, EnsureAddressOrigin};
+use sp_core::H160;
+use sp_io::hashing::keccak_256;
+use sp_runtime::{traits::Zero, DispatchResult};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Convert;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The account an address acts as until it is claimed.
+		type FallbackMapping: AddressMapping<Self::AccountId>;
+
+		/// The address an account gets with [`Pallet::claim_default_address`].
+		type DefaultAddress: Convert<Self::AccountId, H160>;
+
+		/// The currency the funds of the fallback accounts are moved in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The account each claimed address acts as.
+	#[pallet::storage]
+	#[pallet::getter(fn account)]
+	pub type Accounts<T: Config> = StorageMap<_, Blake2_128Concat, H160, T::AccountId>;
+
+	/// The address each account claimed.
+	#[pallet::storage]
+	#[pallet::getter(fn address_of)]
+	pub type Addresses<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, H160>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `account` claimed `address`.
+		AddressClaimed { account: T::AccountId, address: H160 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account already claimed an address.
+		AccountAlreadyLinked,
+		/// Another account already claimed the address.
+		AddressAlreadyClaimed,
+		/// The signature was not made by the key of the address.
+		InvalidSignature,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Claims `address` for the signer.
+		///
+		/// `signature` is the `personal_sign` signature of [`Pallet::claim_message`] by the
+		/// Ethereum key of `address`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::claim_address())]
+		pub fn claim_address(
+			origin: OriginFor<T>,
+			address: H160,
+			signature: [u8; 65],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::signer(&Self::claim_message(&who), &signature) == Some(address),
+				Error::<T>::InvalidSignature
+			);
+			Self::link(who, address)
+		}
+
+		/// Claims the address given by [`Config::DefaultAddress`] for the signer.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::claim_default_address())]
+		pub fn claim_default_address(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let address = T::DefaultAddress::convert(who.clone());
+			Self::link(who, address)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The account `address` acts as: the account that claimed it, or its fallback account.
+	pub fn account_of(address: H160) -> T::AccountId {
+		Accounts::<T>::get(address).unwrap_or_else(|| T::FallbackMapping::into_account_id(address))
+	}
+
+	/// The message the Ethereum key of an address signs to claim it for `who`.
+	pub fn claim_message(who: &T::AccountId) -> Vec<u8> {
+		const HEX: &[u8; 16] = b"0123456789abcdef";
+
+		let mut message = b"Claim for the LUNES account 0x".to_vec();
+		for byte in who.encode() {
+			message.extend([HEX[(byte >> 4) as usize], HEX[(byte & 0xf) as usize]]);
+		}
+		message
+	}
+
+	/// The address whose key made `signature`, a `personal_sign` signature of `message`.
+	pub fn signer(message: &[u8], signature: &[u8; 65]) -> Option<H160> {
+		let mut signed = b"\x19Ethereum Signed Message:\n".to_vec();
+		signed.extend(decimal(message.len()));
+		signed.extend(message);
+		let public =
+			sp_io::crypto::secp256k1_ecdsa_recover(signature, &keccak_256(&signed)).ok()?;
+		Some(H160::from_slice(&keccak_256(&public)[12..]))
+	}
+
+	fn link(who: T::AccountId, address: H160) -> DispatchResult {
+		ensure!(!Addresses::<T>::contains_key(&who), Error::<T>::AccountAlreadyLinked);
+		ensure!(!Accounts::<T>::contains_key(address), Error::<T>::AddressAlreadyClaimed);
+
+		// The address keeps its funds, and its nonce so that it cannot deploy contracts to the
+		// addresses of contracts it created before.
+		let fallback = T::FallbackMapping::into_account_id(address);
+		if fallback != who {
+			let nonce = frame_system::Pallet::<T>::account_nonce(&fallback);
+			if nonce > frame_system::Pallet::<T>::account_nonce(&who) {
+				frame_system::Account::<T>::mutate(&who, |account| account.nonce = nonce);
+			}
+			let balance = T::Currency::free_balance(&fallback);
+			if !balance.is_zero() {
+				T::Currency::transfer(&fallback, &who, balance, ExistenceRequirement::AllowDeath)?;
+			}
+		}
+
+		Accounts::<T>::insert(address, &who);
+		Addresses::<T>::insert(&who, address);
+		Self::deposit_event(Event::AddressClaimed { account: who, address });
+		Ok(())
+	}
+}
+
+/// The decimal digits of `n`.
+fn decimal(mut n: usize) -> Vec<u8> {
+	let mut digits = Vec::new();
+	loop {
+		digits.push(b'0' + (n % 10) as u8);
+		n /= 10;
+		if n == 0 {
+			break
+		}
+	}
+	digits.reverse();
+	digits
+}
+
+impl<T: Config> AddressMapping<T::AccountId> for Pallet<T> {
+	fn into_account_id(address: H160) -> T::AccountId {
+		Self::account_of(address)
+	}
+}
+
+/// Lets an account act in the EVM only as the address it claimed.
+pub struct EnsureAddressClaimed<T>(PhantomData<T>);
+
+impl<T: Config, OuterOrigin> EnsureAddressOrigin<OuterOrigin> for EnsureAddressClaimed<T>
+where
+	OuterOrigin: Into<Result<RawOrigin<T::AccountId>, OuterOrigin>> + From<RawOrigin<T::AccountId>>,
+{
+	type Success = T::AccountId;
+
+	fn try_address_origin(
+		address: &H160,
+		origin: OuterOrigin,
+	) -> Result<T::AccountId, OuterOrigin> {
+		origin.into().and_then(|o| match o {
+			RawOrigin::Signed(who) if Addresses::<T>::get(&who) == Some(*address) => Ok(who),
+			r => Err(OuterOrigin::from(r)),
+		})
+	}
+}
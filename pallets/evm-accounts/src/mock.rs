@@ -0,0 +1,103 @@
+use crate as pallet_evm_accounts;
+use frame_support::traits::{ConstU128, ConstU16, ConstU32, ConstU64};
+use pallet_evm::AddressMapping;
+use sp_core::{H160, H256};
+use sp_keystore::{testing::KeyStore, KeystoreExt};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Convert, IdentityLookup},
+};
+use std::sync::Arc;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		EvmAccounts: pallet_evm_accounts,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u128>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u128;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+/// Unclaimed addresses act as the account of their last 8 bytes, moved out of the way of the
+/// accounts the tests sign with.
+pub struct FallbackMapping;
+
+impl AddressMapping<u64> for FallbackMapping {
+	fn into_account_id(address: H160) -> u64 {
+		address.to_low_u64_be().wrapping_add(FALLBACK_OFFSET)
+	}
+}
+
+pub const FALLBACK_OFFSET: u64 = 1 << 32;
+
+/// The default address of an account is the account itself.
+pub struct DefaultAddress;
+
+impl Convert<u64, H160> for DefaultAddress {
+	fn convert(who: u64) -> H160 {
+		H160::from_low_u64_be(who)
+	}
+}
+
+impl pallet_evm_accounts::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type FallbackMapping = FallbackMapping;
+	type DefaultAddress = DefaultAddress;
+	type Currency = Balances;
+	type WeightInfo = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut ext: sp_io::TestExternalities =
+		frame_system::GenesisConfig::default().build_storage::<Test>().unwrap().into();
+	// The benchmarks sign their claims with a key of the keystore.
+	ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+	ext
+}
@@ -0,0 +1,143 @@
+use crate::{mock::*, EnsureAddressClaimed, Error, Event};
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use pallet_evm::EnsureAddressOrigin;
+use sp_core::{ecdsa, Pair, H160};
+use sp_io::hashing::keccak_256;
+use std::str::FromStr;
+
+fn key(seed: &str) -> ecdsa::Pair {
+	ecdsa::Pair::from_string(seed, None).unwrap()
+}
+
+/// The Ethereum address of `key`, recovered from a signature of an arbitrary hash.
+fn address(key: &ecdsa::Pair) -> H160 {
+	let hash = [7; 32];
+	let public =
+		sp_io::crypto::secp256k1_ecdsa_recover(&key.sign_prehashed(&hash).0, &hash).unwrap();
+	H160::from_slice(&keccak_256(&public)[12..])
+}
+
+/// The `personal_sign` signature by `key` of the claim message of `who`.
+fn sign_claim(key: &ecdsa::Pair, who: u64) -> [u8; 65] {
+	let message = EvmAccounts::claim_message(&who);
+	let mut signed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+	signed.extend(message);
+	key.sign_prehashed(&keccak_256(&signed)).0
+}
+
+#[test]
+fn addresses_are_derived_like_ethereum_wallets() {
+	// The example account of the web3.js documentation.
+	let key = key("0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+	assert_eq!(address(&key), H160::from_str("2c7536e3605d9c16a7a3d7b1898e529396a65c23").unwrap());
+}
+
+#[test]
+fn claim_address_links_both_ways() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let key = key("//Alice");
+		let address = address(&key);
+		assert_eq!(
+			EvmAccounts::account_of(address),
+			address.to_low_u64_be().wrapping_add(FALLBACK_OFFSET)
+		);
+
+		assert_ok!(EvmAccounts::claim_address(
+			RuntimeOrigin::signed(1),
+			address,
+			sign_claim(&key, 1)
+		));
+		assert_eq!(EvmAccounts::account_of(address), 1);
+		assert_eq!(EvmAccounts::address_of(1), Some(address));
+		System::assert_last_event(Event::AddressClaimed { account: 1, address }.into());
+	});
+}
+
+#[test]
+fn claim_address_checks_the_signature() {
+	new_test_ext().execute_with(|| {
+		let key = key("//Alice");
+		let address = address(&key);
+
+		// Signed for another account.
+		assert_noop!(
+			EvmAccounts::claim_address(RuntimeOrigin::signed(1), address, sign_claim(&key, 2)),
+			Error::<Test>::InvalidSignature
+		);
+		// Signed by another key.
+		assert_noop!(
+			EvmAccounts::claim_address(
+				RuntimeOrigin::signed(1),
+				address,
+				sign_claim(&self::key("//Bob"), 1)
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn addresses_and_accounts_are_claimed_once() {
+	new_test_ext().execute_with(|| {
+		let key = key("//Alice");
+		let address = address(&key);
+		assert_ok!(EvmAccounts::claim_address(
+			RuntimeOrigin::signed(1),
+			address,
+			sign_claim(&key, 1)
+		));
+
+		assert_noop!(
+			EvmAccounts::claim_address(RuntimeOrigin::signed(2), address, sign_claim(&key, 2)),
+			Error::<Test>::AddressAlreadyClaimed
+		);
+		assert_noop!(
+			EvmAccounts::claim_default_address(RuntimeOrigin::signed(1)),
+			Error::<Test>::AccountAlreadyLinked
+		);
+	});
+}
+
+#[test]
+fn claim_default_address_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EvmAccounts::claim_default_address(RuntimeOrigin::signed(2)));
+		assert_eq!(EvmAccounts::address_of(2), Some(H160::from_low_u64_be(2)));
+		assert_eq!(EvmAccounts::account_of(H160::from_low_u64_be(2)), 2);
+	});
+}
+
+#[test]
+fn claiming_moves_the_funds_and_nonce_of_the_fallback_account() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(1);
+		let fallback = EvmAccounts::account_of(address);
+		Balances::make_free_balance_be(&fallback, 100);
+		Balances::make_free_balance_be(&1, 10);
+		for _ in 0..3 {
+			System::inc_account_nonce(fallback);
+		}
+
+		assert_ok!(EvmAccounts::claim_default_address(RuntimeOrigin::signed(1)));
+		assert_eq!(Balances::free_balance(1), 110);
+		assert_eq!(Balances::free_balance(fallback), 0);
+		assert_eq!(System::account_nonce(1), 3);
+	});
+}
+
+#[test]
+fn accounts_act_only_as_their_claimed_address() {
+	new_test_ext().execute_with(|| {
+		let address = H160::from_low_u64_be(1);
+		let ensure = |who: u64, address: &H160| {
+			EnsureAddressClaimed::<Test>::ensure_address_origin(address, RuntimeOrigin::signed(who))
+		};
+		assert!(ensure(1, &address).is_err());
+
+		assert_ok!(EvmAccounts::claim_default_address(RuntimeOrigin::signed(1)));
+		assert_eq!(ensure(1, &address), Ok(1));
+		assert!(ensure(1, &H160::from_low_u64_be(2)).is_err());
+		assert!(ensure(2, &address).is_err());
+	});
+}
@@ -0,0 +1,59 @@
+//! Weights for pallet-evm-accounts.
+//!
+//! Regenerate them from the benchmarks in `benchmarking.rs` with
+//!
+//! ```sh
+//! ./target/release/lunes-node benchmark pallet --chain dev --pallet pallet_evm_accounts \
+//! 	--extrinsic '*' --steps 50 --repeat 20 --output pallets/evm-accounts/src/weights.rs
+//! ```
+//!
+//! Until then, every call weighs as much as its storage accesses, and recovering the signer of a
+//! signature as much as the `ECRecover` precompile charges in gas.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for pallet-evm-accounts.
+pub trait WeightInfo {
+	fn claim_address() -> Weight;
+	fn claim_default_address() -> Weight;
+}
+
+/// Weights for pallet-evm-accounts using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: EvmAccounts Addresses (r:1 w:1)
+	// Storage: EvmAccounts Accounts (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_address() -> Weight {
+		Weight::from_parts(75_000_000, 0).saturating_add(T::DbWeight::get().reads_writes(4, 4))
+	}
+	// Storage: EvmAccounts Addresses (r:1 w:1)
+	// Storage: EvmAccounts Accounts (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_default_address() -> Weight {
+		T::DbWeight::get().reads_writes(4, 4)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: EvmAccounts Addresses (r:1 w:1)
+	// Storage: EvmAccounts Accounts (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_address() -> Weight {
+		Weight::from_parts(75_000_000, 0).saturating_add(RocksDbWeight::get().reads_writes(4, 4))
+	}
+	// Storage: EvmAccounts Addresses (r:1 w:1)
+	// Storage: EvmAccounts Accounts (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn claim_default_address() -> Weight {
+		RocksDbWeight::get().reads_writes(4, 4)
+	}
+}
@@ -0,0 +1,34 @@
+//! Benchmarking setup for pallet-fee-split
+
+use super::*;
+
+use crate::Pallet as FeeSplitPallet;
+use frame_benchmarking::v1::{benchmarks, BenchmarkError};
+use frame_support::traits::EnsureOrigin;
+use sp_runtime::Permill;
+
+benchmarks! {
+	set_split {
+		let origin = T::UpdateOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let split = FeeSplit {
+			author: Permill::from_percent(40),
+			treasury: Permill::from_percent(40),
+			burn: Permill::from_percent(20),
+		};
+	}: _<T::RuntimeOrigin>(origin, split)
+	verify {
+		assert_eq!(Split::<T>::get(), split);
+	}
+
+	set_burn_threshold {
+		let origin = T::UpdateOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let threshold = BalanceOf::<T>::from(1_000u32);
+	}: _<T::RuntimeOrigin>(origin, threshold)
+	verify {
+		assert_eq!(BurnThreshold::<T>::get(), threshold);
+	}
+
+	impl_benchmark_test_suite!(FeeSplitPallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,179 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Splits the fees and tips of every transaction between the block author, the treasury and a
+//! burned share.
+//!
+//! The ratios and the total issuance below which nothing is burned are kept in storage and can
+//! be changed by [`Config::UpdateOrigin`]. Until they are first set, the defaults given by the
+//! runtime apply.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::WeightInfo;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{Currency, Imbalance},
+	RuntimeDebug,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Zero, PerThing, Permill};
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+
+/// Shares of the collected fees, which must add up to 100%.
+#[derive(Clone, Copy, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug, PartialEq, Eq)]
+pub struct FeeSplit {
+	/// Share paid to the author of the block.
+	pub author: Permill,
+	/// Share paid to the treasury.
+	pub treasury: Permill,
+	/// Share burned while the total issuance is above the burn threshold. Below it, this share
+	/// goes to the treasury as well.
+	pub burn: Permill,
+}
+
+impl FeeSplit {
+	/// Whether the shares add up to exactly 100%.
+	pub fn is_complete(&self) -> bool {
+		let parts = self.author.deconstruct() as u64 +
+			self.treasury.deconstruct() as u64 +
+			self.burn.deconstruct() as u64;
+		parts == Permill::one().deconstruct() as u64
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency the fees are paid in.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Origin allowed to change the split and the burn threshold.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The split used until one is set on chain.
+		#[pallet::constant]
+		type DefaultSplit: Get<FeeSplit>;
+
+		/// The burn threshold used until one is set on chain.
+		#[pallet::constant]
+		type DefaultBurnThreshold: Get<BalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::type_value]
+	pub fn SplitOnEmpty<T: Config>() -> FeeSplit {
+		T::DefaultSplit::get()
+	}
+
+	#[pallet::type_value]
+	pub fn BurnThresholdOnEmpty<T: Config>() -> BalanceOf<T> {
+		T::DefaultBurnThreshold::get()
+	}
+
+	/// The current split of the fees.
+	#[pallet::storage]
+	#[pallet::getter(fn split)]
+	pub type Split<T: Config> = StorageValue<_, FeeSplit, ValueQuery, SplitOnEmpty<T>>;
+
+	/// The burn share is only burned while the total issuance is above this amount.
+	#[pallet::storage]
+	#[pallet::getter(fn burn_threshold)]
+	pub type BurnThreshold<T: Config> =
+		StorageValue<_, BalanceOf<T>, ValueQuery, BurnThresholdOnEmpty<T>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The split of the fees was changed.
+		SplitUpdated { split: FeeSplit },
+		/// The burn threshold was changed.
+		BurnThresholdUpdated { threshold: BalanceOf<T> },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The shares of the split do not add up to 100%.
+		IncompleteSplit,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Sets the split of the fees. The shares must add up to 100%.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_split())]
+		pub fn set_split(origin: OriginFor<T>, split: FeeSplit) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(split.is_complete(), Error::<T>::IncompleteSplit);
+
+			Split::<T>::put(split);
+			Self::deposit_event(Event::SplitUpdated { split });
+			Ok(())
+		}
+
+		/// Sets the total issuance above which the burn share is burned.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::set_burn_threshold())]
+		pub fn set_burn_threshold(
+			origin: OriginFor<T>,
+			threshold: BalanceOf<T>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			BurnThreshold::<T>::put(threshold);
+			Self::deposit_event(Event::BurnThresholdUpdated { threshold });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Splits `fees` into the shares of the block author, the treasury and the part to burn,
+	/// in that order.
+	///
+	/// The three parts always add up to `fees`: the treasury receives whatever is left after the
+	/// author and burn shares are rounded down.
+	pub fn split_fees(
+		fees: NegativeImbalanceOf<T>,
+	) -> (NegativeImbalanceOf<T>, NegativeImbalanceOf<T>, NegativeImbalanceOf<T>) {
+		let split = Split::<T>::get();
+		let amount = fees.peek();
+
+		let author = split.author.mul_floor(amount);
+		let burn = if T::Currency::total_issuance() > BurnThreshold::<T>::get() {
+			split.burn.mul_floor(amount)
+		} else {
+			Zero::zero()
+		};
+
+		let (author, rest) = fees.split(author);
+		let (burn, treasury) = rest.split(burn);
+		(author, treasury, burn)
+	}
+}
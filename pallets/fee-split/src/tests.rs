@@ -0,0 +1,94 @@
+use crate::{mock::*, Error, Event, FeeSplit as Split};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Currency, Imbalance},
+};
+use sp_runtime::{DispatchError, Permill};
+
+fn split(author: u32, treasury: u32, burn: u32) -> Split {
+	Split {
+		author: Permill::from_percent(author),
+		treasury: Permill::from_percent(treasury),
+		burn: Permill::from_percent(burn),
+	}
+}
+
+#[test]
+fn defaults_apply_until_set() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(FeeSplit::split(), split(50, 30, 20));
+		assert_eq!(FeeSplit::burn_threshold(), 1_000);
+	});
+}
+
+#[test]
+fn set_split_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(FeeSplit::set_split(RuntimeOrigin::root(), split(80, 10, 10)));
+		assert_eq!(FeeSplit::split(), split(80, 10, 10));
+		System::assert_last_event(Event::SplitUpdated { split: split(80, 10, 10) }.into());
+	});
+}
+
+#[test]
+fn set_split_rejects_incomplete_split() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeSplit::set_split(RuntimeOrigin::root(), split(80, 10, 5)),
+			Error::<Test>::IncompleteSplit
+		);
+		assert_noop!(
+			FeeSplit::set_split(RuntimeOrigin::root(), split(80, 20, 10)),
+			Error::<Test>::IncompleteSplit
+		);
+	});
+}
+
+#[test]
+fn set_burn_threshold_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(FeeSplit::set_burn_threshold(RuntimeOrigin::root(), 42));
+		assert_eq!(FeeSplit::burn_threshold(), 42);
+		System::assert_last_event(Event::BurnThresholdUpdated { threshold: 42 }.into());
+	});
+}
+
+#[test]
+fn updates_require_update_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			FeeSplit::set_split(RuntimeOrigin::signed(1), split(80, 10, 10)),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			FeeSplit::set_burn_threshold(RuntimeOrigin::signed(1), 42),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn split_fees_burns_only_above_threshold() {
+	new_test_ext().execute_with(|| {
+		let (author, treasury, burn) = FeeSplit::split_fees(Balances::issue(100));
+		assert_eq!((author.peek(), treasury.peek(), burn.peek()), (50, 50, 0));
+
+		let _ = Balances::deposit_creating(&1, 1_000);
+		let (author, treasury, burn) = FeeSplit::split_fees(Balances::issue(100));
+		assert_eq!((author.peek(), treasury.peek(), burn.peek()), (50, 30, 20));
+	});
+}
+
+#[test]
+fn split_fees_adds_up_to_fees() {
+	new_test_ext().execute_with(|| {
+		let _ = Balances::deposit_creating(&1, 1_000_000);
+		assert_ok!(FeeSplit::set_split(RuntimeOrigin::root(), split(33, 33, 34)));
+		for fees in [0, 1, 2, 3, 7, 99, 101, 999_983, u64::MAX as u128] {
+			let (author, treasury, burn) = FeeSplit::split_fees(Balances::issue(fees));
+			assert_eq!(author.peek() + treasury.peek() + burn.peek(), fees);
+		}
+	});
+}
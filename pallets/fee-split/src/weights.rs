@@ -0,0 +1,50 @@
+//! Weights for pallet-fee-split.
+//!
+//! Regenerate them from the benchmarks in `benchmarking.rs` with
+//!
+//! ```sh
+//! ./target/release/lunes-node benchmark pallet --chain dev --pallet pallet_fee_split \
+//! 	--extrinsic '*' --steps 50 --repeat 20 --output pallets/fee-split/src/weights.rs
+//! ```
+//!
+//! Until then, every call weighs as much as the single storage write it does.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for pallet-fee-split.
+pub trait WeightInfo {
+	fn set_split() -> Weight;
+	fn set_burn_threshold() -> Weight;
+}
+
+/// Weights for pallet-fee-split using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: FeeSplit Split (r:0 w:1)
+	fn set_split() -> Weight {
+		T::DbWeight::get().writes(1)
+	}
+	// Storage: FeeSplit BurnThreshold (r:0 w:1)
+	fn set_burn_threshold() -> Weight {
+		T::DbWeight::get().writes(1)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: FeeSplit Split (r:0 w:1)
+	fn set_split() -> Weight {
+		RocksDbWeight::get().writes(1)
+	}
+	// Storage: FeeSplit BurnThreshold (r:0 w:1)
+	fn set_burn_threshold() -> Weight {
+		RocksDbWeight::get().writes(1)
+	}
+}
@@ -0,0 +1,46 @@
+;; Forwards its input to `seal_call_chain_extension` and returns the outcome.
+;;
+;; Input: the `u32` id of the chain extension function, followed by the input of that function.
+;; Output: the `u32` returned by the chain extension, followed by what the function wrote to the
+;; output buffer, if anything.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32))
+	)
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) length of the input buffer
+	(data (i32.const 0) "\00\10")
+
+	;; [4, 4100) input buffer
+
+	;; [4100, 4104) length of the output buffer
+	(data (i32.const 4100) "\00\10")
+
+	;; [4104, 4108) return value of the chain extension
+
+	;; [4108, 8204) output buffer
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(call $seal_input (i32.const 4) (i32.const 0))
+		(i32.store
+			(i32.const 4104)
+			(call $seal_call_chain_extension
+				(i32.load (i32.const 4))
+				(i32.const 8)
+				(i32.sub (i32.load (i32.const 0)) (i32.const 4))
+				(i32.const 4108)
+				(i32.const 4100)
+			)
+		)
+		(call $seal_return
+			(i32.const 0)
+			(i32.const 4104)
+			(i32.add (i32.load (i32.const 4100)) (i32.const 4))
+		)
+	)
+)
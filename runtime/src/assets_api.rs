@@ -18,10 +18,26 @@
 
 //! Runtime API definition for assets.
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
+/// Metadata and total supply of an asset.
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct AssetInfo<AssetBalance> {
+	/// Name of the asset, empty if no metadata is set.
+	pub name: Vec<u8>,
+	/// Ticker symbol of the asset, empty if no metadata is set.
+	pub symbol: Vec<u8>,
+	/// Number of decimals used to display balances.
+	pub decimals: u8,
+	/// Total amount of the asset in existence.
+	pub supply: AssetBalance,
+}
+
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait AssetsApi<AccountId, AssetBalance, AssetId>
 	where
 		AccountId: Codec,
@@ -30,5 +46,9 @@ sp_api::decl_runtime_apis! {
 	{
 		/// Returns the list of `AssetId`s and corresponding balance that an `AccountId` has.
 		fn account_balances(account: AccountId) -> Vec<(AssetId, AssetBalance)>;
+
+		/// Returns the metadata and total supply of `asset`, or `None` if it does not exist.
+		#[api_version(2)]
+		fn asset_info(asset: AssetId) -> Option<AssetInfo<AssetBalance>>;
 	}
-}
\ No newline at end of file
+}
\ No newline at end of file
@@ -1,411 +1,192 @@
-use codec::{
-    Decode,
-    Encode,
-    MaxEncodedLen,
-};
-use frame_support::{
-    dispatch::RawOrigin,
-    traits::fungibles::{
-        approvals::{
-            Inspect as AllowanceInspect,
-            Mutate as AllowanceMutate,
-        },
-        Inspect,
-        InspectMetadata,
-        Transfer
-        
-    },
-};
-use pallet_assets::{
-    self
-};
-use pallet_contracts::chain_extension::{
-    ChainExtension,
-    Environment,
-    Ext,
-    InitState,
-    RetVal,
-    SysConfig,
-};
+//! Chain extensions bridging ink! contracts to the runtime's native token pallets.
+//!
+//! Every extension is registered under its own extension ID, which contracts pass in the upper
+//! 16 bits of the `func_id` of `seal_call_chain_extension`. The lower 16 bits select the
+//! function within the extension.
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+mod native;
+mod psp22;
+mod psp34;
+pub mod weights;
+
+pub use native::NativeExtension;
+pub use psp22::{Psp22Error, Psp22Extension, Psp22StatusExtension};
+pub use psp34::Psp34Extension;
+
+use codec::Encode;
+use frame_support::{traits::Get, weights::Weight};
+use pallet_contracts::chain_extension::{BufInBufOutState, Environment, Ext, SysConfig};
 use sp_core::crypto::UncheckedFrom;
-use sp_runtime::{
-    traits::{
-        Saturating,
-        StaticLookup,
-        Zero,
-    },
-    DispatchError,
-};
+use sp_io::hashing::blake2_256;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+use weights::WeightInfo;
 
-#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
-struct Psp22BalanceOfInput<AssetId, AccountId> {
-    asset_id: AssetId,
-    owner: AccountId,
-}
-
-#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
-struct Psp22AllowanceInput<AssetId, AccountId> {
-    asset_id: AssetId,
-    owner: AccountId,
-    spender: AccountId,
-}
-
-#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
-struct Psp22TransferInput<AssetId, AccountId, Balance> {
-    asset_id: AssetId,
-    to: AccountId,
-    value: Balance,
-}
+/// All chain extensions available to contracts: PSP22 under ID `0`, PSP34 under ID `1`, native
+/// LUNES under ID `2` and PSP22 with status codes under ID `3`.
+pub type LunesChainExtension =
+	(Psp22Extension, Psp34Extension, NativeExtension, Psp22StatusExtension);
 
-#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
-struct Psp22TransferFromInput<AssetId, AccountId, Balance> {
-    asset_id: AssetId,
-    from: AccountId,
-    to: AccountId,
-    value: Balance,
-}
+/// Weights of the chain extension functions, benchmarked through the forwarding contract.
+type ExtensionWeightInfo<T> = weights::SubstrateWeight<T>;
 
-#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
-struct Psp22ApproveInput<AssetId, AccountId, Balance> {
-    asset_id: AssetId,
-    spender: AccountId,
-    value: Balance,
-}
-
-#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
-struct Psp22BurnInput<AssetId, AccountId, Balance> {
-    asset_id: AssetId,
-    from: AccountId,
-    value: Balance,
-}
-
-#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
-struct Psp22MintInput<AssetId, AccountId, Balance> {
-    asset_id: AssetId,
-    to: AccountId,
-    value: Balance,
-}
-
-#[derive(Default)]
-pub struct Psp22Extension;
+/// The forwarding contract of `fixtures/chain_extension.wat`, compiled by the build script.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+const FORWARDER: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/chain_extension.wasm"));
 
 fn convert_err(err_msg: &'static str) -> impl FnOnce(DispatchError) -> DispatchError {
-    move |_err| {     
-        DispatchError::Other(err_msg)
-    }
+	move |_err| DispatchError::Other(err_msg)
 }
 
-/// We're using enums for function IDs because contrary to raw u16 it enables
-/// exhaustive matching, which results in cleaner code.
-enum FuncId {
-    Metadata(Metadata),
-    Query(Query),
-    Transfer,
-    TransferFrom,
-    Approve,
-    IncreaseAllowance,
-    DecreaseAllowance,
-    Burn,
-    Mint,
-}
-
-#[derive(Debug)]
-enum Metadata {
-    Name,
-    Symbol,
-    Decimals,
-}
-
-#[derive(Debug)]
-enum Query {
-    TotalSupply,
-    BalanceOf,
-    Allowance,
-}
-
-impl TryFrom<u16> for FuncId {
-    type Error = DispatchError;
-
-    fn try_from(func_id: u16) -> Result<Self, Self::Error> {
-        let id = match func_id {
-            // Note: We use the first two bytes of PSP22 interface selectors as function IDs,
-            // While we can use anything here, it makes sense from a convention perspective.
-            0x3d26 => Self::Metadata(Metadata::Name),
-            0x3420 => Self::Metadata(Metadata::Symbol),
-            0x7271 => Self::Metadata(Metadata::Decimals),
-            0x162d => Self::Query(Query::TotalSupply),
-            0x6568 => Self::Query(Query::BalanceOf),
-            0x4d47 => Self::Query(Query::Allowance),
-            0xdb20 => Self::Transfer,
-            0x54b3 => Self::TransferFrom,
-            0xb20f => Self::Approve,
-            0x96d6 => Self::IncreaseAllowance,
-            0xfecb => Self::DecreaseAllowance,
-            0x9e55 => Self::Burn,
-            0x6bba => Self::Mint,
-            _ => {
-                return Err(DispatchError::Other("Unimplemented func_id"))
-            }
-        };
-
-        Ok(id)
-    }
-}
-
-fn metadata<T, E>(
-    func_id: Metadata,
-    env: Environment<E, InitState>,
+/// Charges `weight` for the work done by the runtime plus the cost of copying the input of the
+/// call out of the contract memory, priced like any other host function input.
+///
+/// `weight` is a benchmarked [`ExtensionWeightInfo`], which includes calling the forwarding
+/// contract. `pallet_contracts` charges for that part on its own, so it is left out here.
+fn charge_weight<T, E>(
+	env: &mut Environment<E, BufInBufOutState>,
+	weight: Weight,
 ) -> Result<(), DispatchError>
 where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-    E: Ext<T = T>,
-{
-    let mut env = env.buf_in_buf_out();
-    let asset_id = env.read_as()?;
-    let result = match func_id {
-        Metadata::Name => {
-            <pallet_assets::Pallet<T> as InspectMetadata<T::AccountId>>::name(&asset_id)
-                .encode()
-        }
-        Metadata::Symbol => {
-            <pallet_assets::Pallet<T> as InspectMetadata<T::AccountId>>::symbol(&asset_id)
-                .encode()
-        }
-        Metadata::Decimals => {
-            <pallet_assets::Pallet<T> as InspectMetadata<T::AccountId>>::decimals(
-                &asset_id,
-            )
-            .encode()
-        }
-    };    
-    env.write(&result, false, None)
-        .map_err(convert_err("ChainExtension failed to call PSP22Metadata"))
-}
-
-fn query<T, E>(
-    func_id: Query,
-    env: Environment<E, InitState>,
-) -> Result<(), DispatchError>
-where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-    E: Ext<T = T>,
-{
-    let mut env = env.buf_in_buf_out();
-    let result = match func_id {
-        Query::TotalSupply => {
-            let asset_id = env.read_as()?;
-            <pallet_assets::Pallet<T> as Inspect<T::AccountId>>::total_issuance(asset_id)
-        }
-        Query::BalanceOf => {
-            let input: Psp22BalanceOfInput<T::AssetId, T::AccountId> = env.read_as()?;
-            <pallet_assets::Pallet<T> as Inspect<T::AccountId>>::balance(
-                input.asset_id,
-                &input.owner,
-            )
-        }
-        Query::Allowance => {
-            let input: Psp22AllowanceInput<T::AssetId, T::AccountId> = env.read_as()?;
-            <pallet_assets::Pallet<T> as AllowanceInspect<T::AccountId>>::allowance(
-                input.asset_id,
-                &input.owner,
-                &input.spender,
-            )
-        }
-    }
-    .encode();
-    
-    env.write(&result, false, None)
-        .map_err(convert_err("ChainExtension failed to call PSP22 query"))
-}
-
-fn transfer<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
-where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-    E: Ext<T = T>,
+	T: pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
 {
-    let mut env = env.buf_in_buf_out();
-
-    let input: Psp22TransferInput<T::AssetId, T::AccountId, T::Balance> =
-        env.read_as()?;
-    let sender = env.ext().caller();
-
-    <pallet_assets::Pallet<T> as Transfer<T::AccountId>>::transfer(
-        input.asset_id,
-        sender,
-        &input.to,
-        input.value,
-        true,
-    )
-    .map_err(convert_err("ChainExtension failed to call transfer"))?;
-    
+	let input_per_byte =
+		<T as pallet_contracts::Config>::Schedule::get().host_fn_weights.input_per_byte;
+	let input_weight = input_per_byte.saturating_mul(env.in_len().into());
+	let weight = weight.saturating_sub(ExtensionWeightInfo::<T>::forward());
+	env.charge_weight(weight.saturating_add(input_weight))?;
 
-    Ok(())
+	Ok(())
 }
 
-fn transfer_from<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
-where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-    E: Ext<T = T>,
-{
-    let mut env = env.buf_in_buf_out();    
-   
-
-    let input: Psp22TransferFromInput<T::AssetId, T::AccountId, T::Balance> =
-        env.read_as()?;
-    let spender = env.ext().caller();
-
-    let result =
-        <pallet_assets::Pallet<T> as AllowanceMutate<T::AccountId>>::transfer_from(
-            input.asset_id,
-            &input.from,
-            spender,
-            &input.to,
-            input.value,
-        );
-   
-    result.map_err(convert_err("ChainExtension failed to call transfer_from"))
+/// Weight charged for every byte handed back to the contract.
+fn return_per_byte<T: pallet_contracts::Config>() -> Weight {
+	<T as pallet_contracts::Config>::Schedule::get().host_fn_weights.return_per_byte
 }
 
-fn approve<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
-where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-    E: Ext<T = T>,
-{
-    let mut env = env.buf_in_buf_out();
-      
+/// Computes a topic the way ink! does: `path` followed by the encoded `value`, used as is if it
+/// fits in a hash and hashed with blake2x256 otherwise.
+fn topic<T: SysConfig>(path: &[u8], value: &impl Encode) -> T::Hash {
+	let mut encoded = path.to_vec();
+	value.encode_to(&mut encoded);
 
-    let input: Psp22ApproveInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
-    let owner = env.ext().caller();
-
-    let result = <pallet_assets::Pallet<T> as AllowanceMutate<T::AccountId>>::approve(
-        input.asset_id,
-        owner,
-        &input.spender,
-        input.value,
-    );
-   
-    result.map_err(convert_err("ChainExtension failed to call approve"))
+	let mut topic = T::Hash::default();
+	let len = topic.as_ref().len();
+	if encoded.len() > len {
+		encoded = blake2_256(&encoded).to_vec();
+	}
+	let copied = encoded.len().min(len);
+	topic.as_mut()[..copied].copy_from_slice(&encoded[..copied]);
+	topic
 }
 
-fn decrease_allowance<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
-where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-    E: Ext<T = T>,
-{
-    let mut env = env.buf_in_buf_out();
-    let input: Psp22ApproveInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
-    if input.value.is_zero() {
-        return Ok(())
-    }
-   
-
-    let owner = env.ext().caller();
-    let mut allowance =
-        <pallet_assets::Pallet<T> as AllowanceInspect<T::AccountId>>::allowance(
-            input.asset_id,
-            owner,
-            &input.spender,
-        );
-    <pallet_assets::Pallet<T>>::cancel_approval(
-        RawOrigin::Signed(owner.clone()).into(),
-        input.asset_id.into(),
-        T::Lookup::unlookup(input.spender.clone()),
-    )
-    .map_err(convert_err(
-        "ChainExtension failed to call decrease_allowance",
-    ))?;
-    allowance.saturating_reduce(input.value);
-    if allowance.is_zero() {       
-        return Ok(())
-    }
-    <pallet_assets::Pallet<T> as AllowanceMutate<T::AccountId>>::approve(
-        input.asset_id,
-        owner,
-        &input.spender,
-        allowance,
-    )
-    .map_err(convert_err(
-        "ChainExtension failed to call decrease_allowance",
-    ))?;
-   
-
-    Ok(())
-}
-fn burn<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
-where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-    E: Ext<T = T>,
-{
-    let mut env = env.buf_in_buf_out();
-    let input: Psp22BurnInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
-    let caller = env.ext().caller();
-
-    <pallet_assets::Pallet<T>>::burn(
-        RawOrigin::Signed(caller.clone()).into(),
-        input.asset_id.into(),
-        T::Lookup::unlookup(input.from.clone()),
-        input.value,
-    ).map_err(convert_err("ChainExtension failed to call burn"))?;
-
-    Ok(())
-}
-
-fn mint<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+/// Deposits an event for the calling contract, charging for it like `seal_deposit_event`.
+fn deposit_contract_event<T, E>(
+	env: &mut Environment<E, BufInBufOutState>,
+	topics: Vec<T::Hash>,
+	data: Vec<u8>,
+) -> Result<(), DispatchError>
 where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-    E: Ext<T = T>,
+	T: pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
 {
-    let mut env = env.buf_in_buf_out();
-    let input: Psp22MintInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
-    let caller = env.ext().caller();
-
-    <pallet_assets::Pallet<T>>::mint(
-        RawOrigin::Signed(caller.clone()).into(),
-        input.asset_id.into(),
-        T::Lookup::unlookup(input.to.clone()),
-        input.value,
-    ).map_err(convert_err("ChainExtension failed to call mint"))?;
-
-    Ok(())
+	let host_fn_weights = <T as pallet_contracts::Config>::Schedule::get().host_fn_weights;
+	env.charge_weight(
+		host_fn_weights
+			.deposit_event
+			.saturating_add(
+				host_fn_weights.deposit_event_per_topic.saturating_mul(topics.len() as u64),
+			)
+			.saturating_add(
+				host_fn_weights.deposit_event_per_byte.saturating_mul(data.len() as u64),
+			),
+	)?;
+	env.ext().deposit_event(topics, data);
+
+	Ok(())
+}
+
+/// Calls the chain extensions through a deployed contract, see `fixtures/chain_extension.wat`.
+#[cfg(test)]
+mod test_utils {
+	use super::FORWARDER;
+	use crate::{AccountId, Balance, Contracts, Runtime, RuntimeBlockWeights, System};
+	use codec::{Decode, Encode};
+	use frame_support::traits::GenesisBuild;
+	use pallet_contracts_primitives::{Code, ContractExecResult};
+	use sp_runtime::DispatchError;
+
+	pub fn new_test_ext(balances: Vec<(AccountId, Balance)>) -> sp_io::TestExternalities {
+		let mut storage =
+			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(storage);
+		// Events are not stored in the genesis block.
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+
+	/// Instantiates the forwarding contract on behalf of `origin`, endowing it with `value`.
+	pub fn deploy(origin: &AccountId, value: Balance) -> AccountId {
+		Contracts::bare_instantiate(
+			origin.clone(),
+			value,
+			RuntimeBlockWeights::get().max_block,
+			None,
+			Code::Upload(FORWARDER.to_vec()),
+			vec![],
+			vec![],
+			false,
+		)
+		.result
+		.unwrap()
+		.account_id
+	}
+
+	/// Outcome of a chain extension call made through the forwarding contract.
+	pub struct Call {
+		/// Status code returned by the chain extension.
+		pub status: u32,
+		/// Output written by the chain extension, zeroed if it wrote none.
+		pub output: Vec<u8>,
+		/// Gas consumed by the whole contract call.
+		pub gas_consumed: u64,
+	}
+
+	/// Has `origin` call function `func_id` of the extension `extension_id` through `contract`.
+	///
+	/// Fails if the chain extension traps the contract.
+	pub fn call(
+		origin: &AccountId,
+		contract: &AccountId,
+		extension_id: u16,
+		func_id: u16,
+		input: impl Encode,
+	) -> Result<Call, DispatchError> {
+		let id = (extension_id as u32) << 16 | func_id as u32;
+		let ContractExecResult { gas_consumed, result, .. } = Contracts::bare_call(
+			origin.clone(),
+			contract.clone(),
+			0,
+			RuntimeBlockWeights::get().max_block,
+			None,
+			(id, input).encode(),
+			false,
+			pallet_contracts::Determinism::Deterministic,
+		);
+		let data = result?.data;
+		Ok(Call {
+			status: u32::decode(&mut &data[..4]).unwrap(),
+			output: data[4..].to_vec(),
+			gas_consumed: gas_consumed.ref_time(),
+		})
+	}
 }
-
-impl<T> ChainExtension<T> for Psp22Extension
-where
-    T: pallet_assets::Config + pallet_contracts::Config,
-    <T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
-{
-    fn call<E: Ext>(
-        &mut self,
-        env: Environment<E, InitState>,
-    ) -> Result<RetVal, DispatchError>
-    where
-        E: Ext<T = T>,
-        <E::T as SysConfig>::AccountId:
-            UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
-    {
-        let func_id = FuncId::try_from(env.func_id())?;
-        match func_id {
-            FuncId::Metadata(func_id) => metadata::<T, E>(func_id, env)?,
-            FuncId::Query(func_id) => query::<T, E>(func_id, env)?,
-            FuncId::Transfer => transfer::<T, E>(env)?,
-            FuncId::TransferFrom => transfer_from::<T, E>(env)?,
-            // This is a bit of a shortcut. It was made because the documentation
-            // for Mutate::approve does not specify the result of subsequent calls.
-            FuncId::Approve | FuncId::IncreaseAllowance => approve::<T, E>(env)?,
-            FuncId::DecreaseAllowance => decrease_allowance(env)?,
-            FuncId::Burn => burn(env)?,
-            FuncId::Mint => mint(env)?,
-            
-        }
-
-        Ok(RetVal::Converging(0))
-    }
-}
\ No newline at end of file
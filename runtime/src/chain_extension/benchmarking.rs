@@ -0,0 +1,382 @@
+//! Benchmarks for the chain extension functions.
+//!
+//! Every function is called through the forwarding contract of `fixtures/chain_extension.wat`,
+//! the way contracts call it. The `forward` benchmark measures the contract call alone, which
+//! [`super::charge_weight`] takes off the other weights again.
+
+use super::FORWARDER;
+use crate::{
+	constants::currency::UNIT, AccountId, Assets, Balance, Balances, Contracts, Runtime,
+	RuntimeBlockWeights, RuntimeOrigin,
+};
+use codec::{Decode, Encode};
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::{
+	fungibles::{approvals::Inspect as AllowanceInspect, Inspect},
+	Currency, Get, LockableCurrency, WithdrawReasons,
+};
+use pallet_contracts_primitives::{Code, ExecReturnValue};
+use sp_runtime::DispatchError;
+use sp_std::{marker::PhantomData, prelude::*};
+
+pub struct Pallet<T: Config>(PhantomData<T>);
+
+pub trait Config: frame_system::Config {}
+
+const ASSET: u32 = 1;
+
+fn funded(name: &'static str) -> AccountId {
+	let who: AccountId = account(name, 0, 0);
+	Balances::make_free_balance_be(&who, 1_000_000 * UNIT);
+	who
+}
+
+/// Deploys the forwarding contract for the whitelisted caller, endowed to pay for deposits.
+fn deploy() -> (AccountId, AccountId) {
+	let caller: AccountId = whitelisted_caller();
+	Balances::make_free_balance_be(&caller, 1_000_000 * UNIT);
+	let contract = Contracts::bare_instantiate(
+		caller.clone(),
+		10_000 * UNIT,
+		RuntimeBlockWeights::get().max_block,
+		None,
+		Code::Upload(FORWARDER.to_vec()),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id;
+	(caller, contract)
+}
+
+fn raw_call(
+	caller: &AccountId,
+	contract: &AccountId,
+	extension_id: u16,
+	func_id: u16,
+	input: impl Encode,
+) -> Result<ExecReturnValue, DispatchError> {
+	let id = (extension_id as u32) << 16 | func_id as u32;
+	Contracts::bare_call(
+		caller.clone(),
+		contract.clone(),
+		0,
+		RuntimeBlockWeights::get().max_block,
+		None,
+		(id, input).encode(),
+		false,
+		pallet_contracts::Determinism::Deterministic,
+	)
+	.result
+}
+
+/// Has `caller` call function `func_id` of the extension `extension_id` through `contract`,
+/// returning its output.
+///
+/// Panics unless the function succeeds.
+fn call(
+	caller: &AccountId,
+	contract: &AccountId,
+	extension_id: u16,
+	func_id: u16,
+	input: impl Encode,
+) -> Vec<u8> {
+	let data = raw_call(caller, contract, extension_id, func_id, input).unwrap().data;
+	assert_eq!(u32::decode(&mut &data[..4]).unwrap(), 0);
+	data[4..].to_vec()
+}
+
+/// Creates `ASSET` with `contract` in every role, holding `1_000 * UNIT` of it.
+fn create_asset(contract: &AccountId) {
+	let origin = RuntimeOrigin::signed(contract.clone());
+	Assets::force_create(RuntimeOrigin::root(), ASSET.into(), contract.clone().into(), true, 1)
+		.unwrap();
+	Assets::mint(origin, ASSET.into(), contract.clone().into(), 1_000 * UNIT).unwrap();
+}
+
+/// Has `contract` create a collection it administers through the PSP34 extension.
+fn create_collection(caller: &AccountId, contract: &AccountId) -> u32 {
+	let output = call(caller, contract, 1, 0x0001, (contract, None::<u32>));
+	u32::decode(&mut &output[..]).unwrap()
+}
+
+/// The longest attribute key and value `pallet_nfts` accepts.
+fn attribute() -> (Vec<u8>, Vec<u8>) {
+	let key_limit = <Runtime as pallet_nfts::Config>::KeyLimit::get();
+	let value_limit = <Runtime as pallet_nfts::Config>::ValueLimit::get();
+	(vec![b'k'; key_limit as usize], vec![b'v'; value_limit as usize])
+}
+
+/// The longest name or symbol `pallet_assets` accepts.
+fn metadata_string() -> Vec<u8> {
+	vec![b'm'; <Runtime as pallet_assets::Config>::StringLimit::get() as usize]
+}
+
+benchmarks! {
+	forward {
+		let (caller, contract) = deploy();
+	}: {
+		// No extension has the function `0xffff`, so the contract traps right after calling it.
+		assert!(raw_call(&caller, &contract, 0, 0xffff, ()).is_err());
+	}
+
+	psp22_metadata {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let name = metadata_string();
+		Assets::set_metadata(
+			RuntimeOrigin::signed(contract.clone()),
+			ASSET.into(),
+			name.clone(),
+			name,
+			18,
+		)
+		.unwrap();
+	}: {
+		call(&caller, &contract, 3, 0x3d26, ASSET);
+	}
+
+	psp22_query {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let spender = funded("spender");
+		Assets::approve_transfer(
+			RuntimeOrigin::signed(contract.clone()),
+			ASSET.into(),
+			spender.clone().into(),
+			UNIT,
+		)
+		.unwrap();
+	}: {
+		call(&caller, &contract, 3, 0x4d47, (ASSET, &contract, &spender));
+	}
+
+	psp22_transfer {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let to: AccountId = account("to", 0, 0);
+	}: {
+		call(&caller, &contract, 3, 0xdb20, (ASSET, &to, UNIT));
+	}
+	verify {
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(ASSET, &to), UNIT);
+	}
+
+	psp22_transfer_from {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let owner = funded("owner");
+		let to: AccountId = account("to", 0, 0);
+		call(&caller, &contract, 3, 0xdb20, (ASSET, &owner, 2 * UNIT));
+		Assets::approve_transfer(
+			RuntimeOrigin::signed(owner.clone()),
+			ASSET.into(),
+			contract.clone().into(),
+			2 * UNIT,
+		)
+		.unwrap();
+	}: {
+		call(&caller, &contract, 3, 0x54b3, (ASSET, &owner, &to, UNIT));
+	}
+	verify {
+		assert_eq!(<Assets as Inspect<AccountId>>::balance(ASSET, &to), UNIT);
+	}
+
+	psp22_change_allowance {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let spender = funded("spender");
+		call(&caller, &contract, 3, 0xb20f, (ASSET, &spender, 2 * UNIT));
+	}: {
+		// Decreasing cancels the approval and approves anew, the most expensive change.
+		call(&caller, &contract, 3, 0xfecb, (ASSET, &spender, UNIT));
+	}
+	verify {
+		let allowance =
+			<Assets as AllowanceInspect<AccountId>>::allowance(ASSET, &contract, &spender);
+		assert_eq!(allowance, UNIT);
+	}
+
+	psp22_burn {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+	}: {
+		call(&caller, &contract, 3, 0x9e55, (ASSET, &contract, UNIT));
+	}
+
+	psp22_mint {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let to: AccountId = account("to", 0, 0);
+	}: {
+		call(&caller, &contract, 3, 0x6bba, (ASSET, &to, UNIT));
+	}
+
+	psp22_create {
+		let (caller, contract) = deploy();
+	}: {
+		call(&caller, &contract, 3, 0x0001, (ASSET, 1 as Balance));
+	}
+
+	psp22_set_metadata {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let name = metadata_string();
+	}: {
+		call(&caller, &contract, 3, 0x0002, (ASSET, &name, &name, 18u8));
+	}
+
+	psp22_set_team {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let team: AccountId = account("team", 0, 0);
+	}: {
+		call(&caller, &contract, 3, 0x0003, (ASSET, &team, &team, &team));
+	}
+
+	psp22_transfer_ownership {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		let owner = funded("owner");
+	}: {
+		call(&caller, &contract, 3, 0x0004, (ASSET, &owner));
+	}
+
+	psp22_freeze {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+	}: {
+		call(&caller, &contract, 3, 0x0005, (ASSET, &contract));
+	}
+
+	psp22_thaw {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		call(&caller, &contract, 3, 0x0005, (ASSET, &contract));
+	}: {
+		call(&caller, &contract, 3, 0x0006, (ASSET, &contract));
+	}
+
+	psp22_freeze_asset {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+	}: {
+		call(&caller, &contract, 3, 0x0007, ASSET);
+	}
+
+	psp22_thaw_asset {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+		call(&caller, &contract, 3, 0x0007, ASSET);
+	}: {
+		call(&caller, &contract, 3, 0x0008, ASSET);
+	}
+
+	psp22_start_destroy {
+		let (caller, contract) = deploy();
+		create_asset(&contract);
+	}: {
+		call(&caller, &contract, 3, 0x0009, ASSET);
+	}
+
+	psp34_create_collection {
+		let (caller, contract) = deploy();
+	}: {
+		create_collection(&caller, &contract);
+	}
+
+	psp34_query {
+		let (caller, contract) = deploy();
+		let collection = create_collection(&caller, &contract);
+		let (key, value) = attribute();
+		call(&caller, &contract, 1, 0x6c41, (collection, 0u32, &contract));
+		call(&caller, &contract, 1, 0x0002, (collection, 0u32, &key, &value));
+	}: {
+		call(&caller, &contract, 1, 0xf19d, (collection, 0u32, &key));
+	}
+
+	psp34_balance_of {
+		let n in 0 .. 100;
+		let (caller, contract) = deploy();
+		let collection = create_collection(&caller, &contract);
+		for item in 0..n {
+			call(&caller, &contract, 1, 0x6c41, (collection, item, &contract));
+		}
+	}: {
+		call(&caller, &contract, 1, 0xcde7, (collection, &contract));
+	}
+
+	psp34_mint {
+		let (caller, contract) = deploy();
+		let collection = create_collection(&caller, &contract);
+		let to: AccountId = account("to", 0, 0);
+	}: {
+		call(&caller, &contract, 1, 0x6c41, (collection, 0u32, &to));
+	}
+
+	psp34_burn {
+		let (caller, contract) = deploy();
+		let collection = create_collection(&caller, &contract);
+		call(&caller, &contract, 1, 0x6c41, (collection, 0u32, &contract));
+	}: {
+		call(&caller, &contract, 1, 0x63c9, (collection, 0u32));
+	}
+
+	psp34_transfer {
+		let (caller, contract) = deploy();
+		let collection = create_collection(&caller, &contract);
+		let to: AccountId = account("to", 0, 0);
+		call(&caller, &contract, 1, 0x6c41, (collection, 0u32, &contract));
+	}: {
+		call(&caller, &contract, 1, 0x3128, (collection, 0u32, &to));
+	}
+
+	psp34_approve {
+		let (caller, contract) = deploy();
+		let collection = create_collection(&caller, &contract);
+		let operator: AccountId = account("operator", 0, 0);
+		call(&caller, &contract, 1, 0x6c41, (collection, 0u32, &contract));
+	}: {
+		call(&caller, &contract, 1, 0x1932, (collection, 0u32, &operator, true));
+	}
+
+	psp34_set_attribute {
+		let (caller, contract) = deploy();
+		let collection = create_collection(&caller, &contract);
+		let (key, value) = attribute();
+		call(&caller, &contract, 1, 0x6c41, (collection, 0u32, &contract));
+	}: {
+		call(&caller, &contract, 1, 0x0002, (collection, 0u32, &key, &value));
+	}
+
+	native_query {
+		let (caller, contract) = deploy();
+		let owner = funded("owner");
+		// Listing the locks is the most expensive query.
+		let max_locks: u32 = <Runtime as pallet_balances::Config>::MaxLocks::get();
+		for id in 0..max_locks {
+			let id = (id as u64).to_le_bytes();
+			Balances::set_lock(id, &owner, UNIT, WithdrawReasons::all());
+		}
+	}: {
+		call(&caller, &contract, 2, 0x0003, &owner);
+	}
+
+	native_transfer {
+		let (caller, contract) = deploy();
+		let to: AccountId = account("to", 0, 0);
+	}: {
+		call(&caller, &contract, 2, 0xdb20, (&to, UNIT));
+	}
+	verify {
+		assert_eq!(Balances::free_balance(&to), UNIT);
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		super::test_utils::new_test_ext(Vec::new()),
+		crate::Runtime
+	);
+}
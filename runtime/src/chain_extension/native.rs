@@ -0,0 +1,295 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchResult,
+	storage::with_storage_layer,
+	traits::{fungible::Inspect, Currency, ExistenceRequirement, PalletInfo, ReservableCurrency},
+};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal, SysConfig,
+};
+use sp_core::crypto::UncheckedFrom;
+use sp_runtime::{DispatchError, ModuleError};
+use sp_std::{vec, vec::Vec};
+
+use super::{
+	charge_weight, convert_err, deposit_contract_event, return_per_byte, topic,
+	weights::WeightInfo, ExtensionWeightInfo, Psp22Error,
+};
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct NativeReducibleBalanceInput<AccountId> {
+	owner: AccountId,
+	keep_alive: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct NativeTransferInput<AccountId, Balance> {
+	to: AccountId,
+	value: Balance,
+}
+
+/// Events deposited under the address of the calling contract, laid out like the `Transfer`
+/// event of an ink! contract named `Native`.
+#[derive(Encode)]
+enum NativeEvent<AccountId, Balance> {
+	#[codec(index = 0)]
+	Transfer { from: Option<AccountId>, to: Option<AccountId>, value: Balance },
+}
+
+/// Gives contracts access to native LUNES held in `pallet_balances`.
+///
+/// The functions mirror their [`super::Psp22StatusExtension`] counterparts without the asset id,
+/// so contracts can handle LUNES and issued assets the same way. Transfers report a [`Psp22Error`]
+/// status code and, like `seal_transfer`, only ever move the funds of the contract itself.
+///
+/// There is no `approve`, `transfer_from` or `allowance`, as `pallet_balances` keeps no
+/// allowances. Users pay LUNES to a contract by transferring them along with their call.
+#[derive(Default)]
+pub struct NativeExtension;
+
+enum FuncId {
+	Query(Query),
+	Transfer,
+}
+
+#[derive(Debug)]
+enum Query {
+	TotalIssuance,
+	BalanceOf,
+	ReducibleBalance,
+	ReservedBalance,
+	Locks,
+}
+
+impl TryFrom<u16> for FuncId {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		let id = match func_id {
+			// Note: The PSP22 selectors are reused where a PSP22 function exists.
+			0x162d => Self::Query(Query::TotalIssuance),
+			0x6568 => Self::Query(Query::BalanceOf),
+			0xdb20 => Self::Transfer,
+			0x0001 => Self::Query(Query::ReducibleBalance),
+			0x0002 => Self::Query(Query::ReservedBalance),
+			0x0003 => Self::Query(Query::Locks),
+			_ => return Err(DispatchError::Other("Unimplemented func_id")),
+		};
+
+		Ok(id)
+	}
+}
+
+fn query<T, E>(func_id: Query, env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_balances::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	// Every query is answered from a single storage entry: `TotalIssuance`, `Account` or `Locks`.
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::native_query())?;
+	let result = match func_id {
+		Query::TotalIssuance =>
+			<pallet_balances::Pallet<T> as Currency<T::AccountId>>::total_issuance().encode(),
+		Query::BalanceOf => {
+			let owner: T::AccountId = env.read_as()?;
+			<pallet_balances::Pallet<T> as Currency<T::AccountId>>::free_balance(&owner).encode()
+		},
+		Query::ReducibleBalance => {
+			let input: NativeReducibleBalanceInput<T::AccountId> = env.read_as()?;
+			<pallet_balances::Pallet<T> as Inspect<T::AccountId>>::reducible_balance(
+				&input.owner,
+				input.keep_alive,
+			)
+			.encode()
+		},
+		Query::ReservedBalance => {
+			let owner: T::AccountId = env.read_as()?;
+			<pallet_balances::Pallet<T> as ReservableCurrency<T::AccountId>>::reserved_balance(
+				&owner,
+			)
+			.encode()
+		},
+		Query::Locks => {
+			let owner: T::AccountId = env.read_as()?;
+			<pallet_balances::Pallet<T>>::locks(&owner)
+				.into_iter()
+				.map(|lock| (lock.id, lock.amount))
+				.collect::<Vec<_>>()
+				.encode()
+		},
+	};
+
+	env.write(&result, false, Some(return_per_byte::<T>()))
+		.map_err(convert_err("ChainExtension failed to call native query"))
+}
+
+fn transfer<T, E>(env: Environment<E, InitState>) -> Result<DispatchResult, DispatchError>
+where
+	T: pallet_balances::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::native_transfer())?;
+	let input: NativeTransferInput<T::AccountId, T::Balance> = env.read_as()?;
+	let contract = env.ext().address().clone();
+
+	// The contract account is kept alive, it can only be removed by terminating the contract.
+	let result = with_storage_layer(|| {
+		<pallet_balances::Pallet<T> as Currency<T::AccountId>>::transfer(
+			&contract,
+			&input.to,
+			input.value,
+			ExistenceRequirement::KeepAlive,
+		)
+	});
+	if let Err(err) = result {
+		return Ok(Err(err))
+	}
+
+	let topics = vec![
+		topic::<T>(b"", b"Native::Transfer"),
+		topic::<T>(b"Native::Transfer::from", &Some(&contract)),
+		topic::<T>(b"Native::Transfer::to", &Some(&input.to)),
+	];
+	let event =
+		NativeEvent::Transfer { from: Some(contract), to: Some(input.to), value: input.value };
+	deposit_contract_event::<T, E>(&mut env, topics, event.encode())?;
+
+	Ok(Ok(()))
+}
+
+fn status<T: pallet_balances::Config>(result: DispatchResult) -> u32 {
+	let err = match result {
+		Ok(()) => return 0,
+		Err(err) => err,
+	};
+
+	let error = match err {
+		DispatchError::Module(ModuleError { index, error, .. })
+			if Some(index as usize) ==
+				<<T as SysConfig>::PalletInfo as PalletInfo>::index::<
+					pallet_balances::Pallet<T>,
+				>() =>
+		{
+			use pallet_balances::Error;
+
+			match Error::<T>::decode(&mut &error[..]) {
+				Ok(Error::InsufficientBalance) => Psp22Error::InsufficientBalance,
+				Ok(Error::LiquidityRestrictions) => Psp22Error::Frozen,
+				Ok(Error::ExistentialDeposit) => Psp22Error::BelowMinimum,
+				Ok(Error::KeepAlive) => Psp22Error::WouldDie,
+				Ok(_) | Err(_) => Psp22Error::Other,
+			}
+		},
+		err => Psp22Error::from_common_error(err),
+	};
+
+	error as u32
+}
+
+impl<T> ChainExtension<T> for NativeExtension
+where
+	T: pallet_balances::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	fn call<E: Ext>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let func_id = FuncId::try_from(env.func_id())?;
+		let result = match func_id {
+			FuncId::Query(func_id) => query::<T, E>(func_id, env).map(Ok)?,
+			FuncId::Transfer => transfer::<T, E>(env)?,
+		};
+
+		Ok(RetVal::Converging(status::<T>(result)))
+	}
+}
+
+impl<T> RegisteredChainExtension<T> for NativeExtension
+where
+	T: pallet_balances::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	const ID: u16 = 0x0002;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		chain_extension::test_utils::{call, deploy, new_test_ext},
+		constants::currency::UNIT,
+		AccountId, Balance, Balances, RuntimeEvent, System,
+	};
+
+	const ALICE: AccountId = AccountId::new([1; 32]);
+	const BOB: AccountId = AccountId::new([2; 32]);
+	const CHARLIE: AccountId = AccountId::new([3; 32]);
+
+	const TRANSFER: u16 = 0xdb20;
+
+	#[test]
+	fn contract_transfers_only_its_own_funds() {
+		new_test_ext(vec![(ALICE, 1_000 * UNIT), (BOB, 100 * UNIT)]).execute_with(|| {
+			let contract = deploy(&ALICE, 10 * UNIT);
+			let contract_balance = Balances::free_balance(&contract);
+
+			// Bob calls the contract, but the LUNES come out of the contract.
+			let result = call(&BOB, &contract, 2, TRANSFER, (CHARLIE, 4 * UNIT)).unwrap();
+			assert_eq!(result.status, 0);
+			assert_eq!(Balances::free_balance(&CHARLIE), 4 * UNIT);
+			assert_eq!(Balances::free_balance(&contract), contract_balance - 4 * UNIT);
+			assert_eq!(Balances::free_balance(&BOB), 100 * UNIT);
+
+			// Bob's balance cannot cover what the contract lacks.
+			let result = call(&BOB, &contract, 2, TRANSFER, (CHARLIE, 50 * UNIT)).unwrap();
+			assert_eq!(result.status, Psp22Error::InsufficientBalance as u32);
+			assert_eq!(Balances::free_balance(&BOB), 100 * UNIT);
+		});
+	}
+
+	#[test]
+	fn transfer_deposits_a_contract_event() {
+		new_test_ext(vec![(ALICE, 1_000 * UNIT)]).execute_with(|| {
+			let contract = deploy(&ALICE, 10 * UNIT);
+			let value: Balance = 4 * UNIT;
+			assert_eq!(call(&ALICE, &contract, 2, TRANSFER, (CHARLIE, value)).unwrap().status, 0);
+
+			let record = System::events()
+				.into_iter()
+				.rev()
+				.find(|record| {
+					matches!(
+						record.event,
+						RuntimeEvent::Contracts(pallet_contracts::Event::ContractEmitted { .. })
+					)
+				})
+				.unwrap();
+			assert_eq!(
+				record.event,
+				RuntimeEvent::Contracts(pallet_contracts::Event::ContractEmitted {
+					contract: contract.clone(),
+					data: NativeEvent::Transfer {
+						from: Some(contract.clone()),
+						to: Some(CHARLIE),
+						value,
+					}
+					.encode(),
+				})
+			);
+			assert_eq!(
+				record.topics,
+				vec![
+					topic::<crate::Runtime>(b"", b"Native::Transfer"),
+					topic::<crate::Runtime>(b"Native::Transfer::from", &Some(contract)),
+					topic::<crate::Runtime>(b"Native::Transfer::to", &Some(CHARLIE)),
+				]
+			);
+		});
+	}
+}
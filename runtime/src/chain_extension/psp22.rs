@@ -0,0 +1,1234 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{DispatchResult, RawOrigin},
+	storage::with_storage_layer,
+	traits::{
+		fungibles::{
+			approvals::{Inspect as AllowanceInspect, Mutate as AllowanceMutate},
+			Inspect, InspectMetadata, Transfer,
+		},
+		PalletInfo,
+	},
+};
+use pallet_contracts::chain_extension::{
+	BufInBufOutState, ChainExtension, Environment, Ext, InitState, RegisteredChainExtension,
+	RetVal, SysConfig,
+};
+use sp_core::crypto::UncheckedFrom;
+use sp_runtime::{
+	traits::{CheckedAdd, CheckedSub, Saturating, StaticLookup, Zero},
+	ArithmeticError, DispatchError, ModuleError, TokenError,
+};
+use sp_std::{vec, vec::Vec};
+
+use super::{
+	charge_weight, convert_err, deposit_contract_event, return_per_byte, topic,
+	weights::WeightInfo, ExtensionWeightInfo,
+};
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22BalanceOfInput<AssetId, AccountId> {
+	asset_id: AssetId,
+	owner: AccountId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22AllowanceInput<AssetId, AccountId> {
+	asset_id: AssetId,
+	owner: AccountId,
+	spender: AccountId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22TransferInput<AssetId, AccountId, Balance> {
+	asset_id: AssetId,
+	to: AccountId,
+	value: Balance,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22TransferFromInput<AssetId, AccountId, Balance> {
+	asset_id: AssetId,
+	from: AccountId,
+	to: AccountId,
+	value: Balance,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22ApproveInput<AssetId, AccountId, Balance> {
+	asset_id: AssetId,
+	spender: AccountId,
+	value: Balance,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22BurnInput<AssetId, AccountId, Balance> {
+	asset_id: AssetId,
+	from: AccountId,
+	value: Balance,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22MintInput<AssetId, AccountId, Balance> {
+	asset_id: AssetId,
+	to: AccountId,
+	value: Balance,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22CreateInput<AssetId, Balance> {
+	asset_id: AssetId,
+	min_balance: Balance,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Psp22SetMetadataInput<AssetId> {
+	asset_id: AssetId,
+	name: Vec<u8>,
+	symbol: Vec<u8>,
+	decimals: u8,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22SetTeamInput<AssetId, AccountId> {
+	asset_id: AssetId,
+	issuer: AccountId,
+	admin: AccountId,
+	freezer: AccountId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22TransferOwnershipInput<AssetId, AccountId> {
+	asset_id: AssetId,
+	owner: AccountId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp22FreezeInput<AssetId, AccountId> {
+	asset_id: AssetId,
+	who: AccountId,
+}
+
+/// Bridges ink! PSP22 contracts to `pallet_assets`.
+///
+/// A failing state changing function traps the contract, and all but the asset management
+/// functions act on behalf of the caller of the contract. This is what contracts deployed before
+/// [`Psp22Error`] rely on. New contracts should use [`Psp22StatusExtension`] instead.
+#[derive(Default)]
+pub struct Psp22Extension;
+
+/// The functions of [`Psp22Extension`], reporting failures as a [`Psp22Error`] status code.
+///
+/// Every state changing function is signed by the contract, like the transfers of
+/// [`super::NativeExtension`]. A contract thus only moves its own assets or those it was approved
+/// for, never those of its caller.
+#[derive(Default)]
+pub struct Psp22StatusExtension;
+
+/// The account that signs the calls to `pallet_assets` made on behalf of a contract.
+#[derive(Clone, Copy, Debug)]
+enum Signer {
+	/// The caller of the contract, as the extension under ID `0` always did.
+	Caller,
+	/// The contract itself.
+	Contract,
+}
+
+impl Signer {
+	fn account<T, E>(self, env: &mut Environment<E, BufInBufOutState>) -> T::AccountId
+	where
+		T: pallet_contracts::Config,
+		<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+		E: Ext<T = T>,
+	{
+		match self {
+			Self::Caller => env.ext().caller().clone(),
+			Self::Contract => env.ext().address().clone(),
+		}
+	}
+}
+
+/// Status codes returned by the state changing functions of [`Psp22StatusExtension`].
+///
+/// The code is handed back to the contract as the return value of `seal_call_chain_extension`,
+/// with `0` meaning success. ink! contracts can mirror this enum and decode the code in their
+/// `FromStatusCode` implementation. The values are part of the contract ABI and must not change.
+/// Malformed input and running out of gas still trap the contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Psp22Error {
+	/// The call failed for a reason not covered by any other code.
+	Other = 1,
+	/// The account does not hold enough of the asset.
+	InsufficientBalance = 2,
+	/// The spender has not been approved for enough of the asset.
+	InsufficientAllowance = 3,
+	/// The asset does not exist.
+	UnknownAsset = 4,
+	/// The asset or the account is frozen, or the asset is being destroyed.
+	Frozen = 5,
+	/// The caller is not allowed to perform the operation, e.g. mint without being the issuer.
+	NoPermission = 6,
+	/// The resulting balance would be below the asset's minimum balance.
+	BelowMinimum = 7,
+	/// The operation would reap the account, which is not allowed.
+	WouldDie = 8,
+	/// The account to alter holds no balance of the asset.
+	NoAccount = 9,
+	/// The receiving account cannot be created.
+	CannotCreate = 10,
+	/// An arithmetic overflow or underflow occurred.
+	Arithmetic = 11,
+}
+
+impl Psp22Error {
+	fn from_dispatch_error<T: pallet_assets::Config>(err: DispatchError) -> Self {
+		match err {
+			DispatchError::Module(ModuleError { index, error, .. })
+				if Some(index as usize) ==
+					<<T as SysConfig>::PalletInfo as PalletInfo>::index::<
+						pallet_assets::Pallet<T>,
+					>() =>
+				match pallet_assets::Error::<T>::decode(&mut &error[..]) {
+					Ok(err) => Self::from_assets_error(err),
+					Err(_) => Self::Other,
+				},
+			err => Self::from_common_error(err),
+		}
+	}
+
+	/// Maps the errors not raised by a specific pallet.
+	pub(super) fn from_common_error(err: DispatchError) -> Self {
+		match err {
+			DispatchError::Token(TokenError::NoFunds) => Self::InsufficientBalance,
+			DispatchError::Token(TokenError::UnknownAsset) => Self::UnknownAsset,
+			DispatchError::Token(TokenError::Frozen) => Self::Frozen,
+			DispatchError::Token(TokenError::BelowMinimum) => Self::BelowMinimum,
+			DispatchError::Token(TokenError::WouldDie) => Self::WouldDie,
+			DispatchError::Token(TokenError::CannotCreate) => Self::CannotCreate,
+			DispatchError::Arithmetic(
+				ArithmeticError::Underflow |
+				ArithmeticError::Overflow |
+				ArithmeticError::DivisionByZero,
+			) => Self::Arithmetic,
+			DispatchError::BadOrigin => Self::NoPermission,
+			_ => Self::Other,
+		}
+	}
+
+	fn from_assets_error<T: pallet_assets::Config>(err: pallet_assets::Error<T>) -> Self {
+		use pallet_assets::Error;
+
+		match err {
+			Error::BalanceLow => Self::InsufficientBalance,
+			Error::Unapproved => Self::InsufficientAllowance,
+			Error::Unknown => Self::UnknownAsset,
+			Error::Frozen | Error::AssetNotLive => Self::Frozen,
+			Error::NoPermission => Self::NoPermission,
+			Error::WouldDie => Self::WouldDie,
+			Error::NoAccount => Self::NoAccount,
+			_ => Self::Other,
+		}
+	}
+
+	/// Turns the outcome of a runtime call into the status code returned to the contract.
+	fn status<T: pallet_assets::Config>(result: DispatchResult) -> u32 {
+		match result {
+			Ok(()) => 0,
+			Err(err) => Self::from_dispatch_error::<T>(err) as u32,
+		}
+	}
+}
+
+/// We're using enums for function IDs because contrary to raw u16 it enables
+/// exhaustive matching, which results in cleaner code.
+enum FuncId {
+	Metadata(Metadata),
+	Query(Query),
+	Transfer,
+	TransferFrom,
+	Approve,
+	IncreaseAllowance,
+	DecreaseAllowance,
+	Burn,
+	Mint,
+	Management(Management),
+}
+
+#[derive(Debug)]
+enum Metadata {
+	Name,
+	Symbol,
+	Decimals,
+}
+
+#[derive(Debug)]
+enum Query {
+	TotalSupply,
+	BalanceOf,
+	Allowance,
+}
+
+/// Asset management functions, dispatched with the calling contract as the signer.
+#[derive(Debug)]
+enum Management {
+	Create,
+	SetMetadata,
+	SetTeam,
+	TransferOwnership,
+	Freeze,
+	Thaw,
+	FreezeAsset,
+	ThawAsset,
+	StartDestroy,
+}
+
+impl TryFrom<u16> for FuncId {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		let id = match func_id {
+			// Note: We use the first two bytes of PSP22 interface selectors as function IDs,
+			// While we can use anything here, it makes sense from a convention perspective.
+			0x3d26 => Self::Metadata(Metadata::Name),
+			0x3420 => Self::Metadata(Metadata::Symbol),
+			0x7271 => Self::Metadata(Metadata::Decimals),
+			0x162d => Self::Query(Query::TotalSupply),
+			0x6568 => Self::Query(Query::BalanceOf),
+			0x4d47 => Self::Query(Query::Allowance),
+			0xdb20 => Self::Transfer,
+			0x54b3 => Self::TransferFrom,
+			0xb20f => Self::Approve,
+			0x96d6 => Self::IncreaseAllowance,
+			0xfecb => Self::DecreaseAllowance,
+			0x9e55 => Self::Burn,
+			0x6bba => Self::Mint,
+			// Asset management is not part of PSP22, so these use IDs outside the selector range.
+			0x0001 => Self::Management(Management::Create),
+			0x0002 => Self::Management(Management::SetMetadata),
+			0x0003 => Self::Management(Management::SetTeam),
+			0x0004 => Self::Management(Management::TransferOwnership),
+			0x0005 => Self::Management(Management::Freeze),
+			0x0006 => Self::Management(Management::Thaw),
+			0x0007 => Self::Management(Management::FreezeAsset),
+			0x0008 => Self::Management(Management::ThawAsset),
+			0x0009 => Self::Management(Management::StartDestroy),
+			_ => return Err(DispatchError::Other("Unimplemented func_id")),
+		};
+
+		Ok(id)
+	}
+}
+
+/// Events deposited under the address of the calling contract, laid out like the events of
+/// an ink! PSP22 contract with the asset id as an additional field.
+#[derive(Encode)]
+enum Psp22Event<AssetId, AccountId, Balance> {
+	/// `from` is `None` for mints and `to` is `None` for burns.
+	#[codec(index = 0)]
+	Transfer { asset_id: AssetId, from: Option<AccountId>, to: Option<AccountId>, value: Balance },
+	#[codec(index = 1)]
+	Approval { asset_id: AssetId, owner: AccountId, spender: AccountId, value: Balance },
+}
+
+/// Deposits `event` for the calling contract.
+///
+/// The [`topic`]s are those of an ink! contract named `PSP22` that marks every field but `value`
+/// as a topic: the event path first, then the asset id and the accounts involved.
+fn deposit_event<T, E>(
+	env: &mut Environment<E, BufInBufOutState>,
+	event: Psp22Event<T::AssetId, T::AccountId, T::Balance>,
+) -> Result<(), DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let topics = match &event {
+		Psp22Event::Transfer { asset_id, from, to, .. } => vec![
+			topic::<T>(b"", b"PSP22::Transfer"),
+			topic::<T>(b"PSP22::Transfer::asset_id", asset_id),
+			topic::<T>(b"PSP22::Transfer::from", from),
+			topic::<T>(b"PSP22::Transfer::to", to),
+		],
+		Psp22Event::Approval { asset_id, owner, spender, .. } => vec![
+			topic::<T>(b"", b"PSP22::Approval"),
+			topic::<T>(b"PSP22::Approval::asset_id", asset_id),
+			topic::<T>(b"PSP22::Approval::owner", owner),
+			topic::<T>(b"PSP22::Approval::spender", spender),
+		],
+	};
+
+	deposit_contract_event::<T, E>(env, topics, event.encode())
+}
+
+fn metadata<T, E>(func_id: Metadata, env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	// Name, symbol and decimals all live in the single `Metadata` entry of the asset.
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp22_metadata())?;
+	let asset_id = env.read_as()?;
+	let result = match func_id {
+		Metadata::Name =>
+			<pallet_assets::Pallet<T> as InspectMetadata<T::AccountId>>::name(&asset_id).encode(),
+		Metadata::Symbol =>
+			<pallet_assets::Pallet<T> as InspectMetadata<T::AccountId>>::symbol(&asset_id).encode(),
+		Metadata::Decimals =>
+			<pallet_assets::Pallet<T> as InspectMetadata<T::AccountId>>::decimals(&asset_id)
+				.encode(),
+	};
+	env.write(&result, false, Some(return_per_byte::<T>()))
+		.map_err(convert_err("ChainExtension failed to call PSP22Metadata"))
+}
+
+fn query<T, E>(func_id: Query, env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	// Every query is answered from a single storage entry: `Asset`, `Account` or `Approvals`.
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp22_query())?;
+	let result = match func_id {
+		Query::TotalSupply => {
+			let asset_id = env.read_as()?;
+			<pallet_assets::Pallet<T> as Inspect<T::AccountId>>::total_issuance(asset_id)
+		},
+		Query::BalanceOf => {
+			let input: Psp22BalanceOfInput<T::AssetId, T::AccountId> = env.read_as()?;
+			<pallet_assets::Pallet<T> as Inspect<T::AccountId>>::balance(
+				input.asset_id,
+				&input.owner,
+			)
+		},
+		Query::Allowance => {
+			let input: Psp22AllowanceInput<T::AssetId, T::AccountId> = env.read_as()?;
+			<pallet_assets::Pallet<T> as AllowanceInspect<T::AccountId>>::allowance(
+				input.asset_id,
+				&input.owner,
+				&input.spender,
+			)
+		},
+	}
+	.encode();
+
+	env.write(&result, false, Some(return_per_byte::<T>()))
+		.map_err(convert_err("ChainExtension failed to call PSP22 query"))
+}
+
+// The state changing functions below return the outcome of the runtime call in the `Ok`
+// variant, while the `Err` variant is reserved for failures that must trap the contract.
+// Runtime calls run in their own storage layer so that a failure leaves no partial changes.
+
+fn transfer<T, E>(
+	signer: Signer,
+	env: Environment<E, InitState>,
+) -> Result<DispatchResult, DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp22_transfer())?;
+
+	let input: Psp22TransferInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
+	let sender = signer.account::<T, E>(&mut env);
+
+	let result = with_storage_layer(|| {
+		<pallet_assets::Pallet<T> as Transfer<T::AccountId>>::transfer(
+			input.asset_id,
+			&sender,
+			&input.to,
+			input.value,
+			true,
+		)
+	});
+	if let Err(err) = result {
+		return Ok(Err(err))
+	}
+
+	deposit_event::<T, E>(
+		&mut env,
+		Psp22Event::Transfer {
+			asset_id: input.asset_id,
+			from: Some(sender),
+			to: Some(input.to),
+			value: input.value,
+		},
+	)?;
+
+	Ok(Ok(()))
+}
+
+fn transfer_from<T, E>(
+	signer: Signer,
+	env: Environment<E, InitState>,
+) -> Result<DispatchResult, DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp22_transfer_from())?;
+
+	let input: Psp22TransferFromInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
+	let spender = signer.account::<T, E>(&mut env);
+
+	let result = with_storage_layer(|| {
+		<pallet_assets::Pallet<T> as AllowanceMutate<T::AccountId>>::transfer_from(
+			input.asset_id,
+			&input.from,
+			&spender,
+			&input.to,
+			input.value,
+		)
+	});
+	if let Err(err) = result {
+		return Ok(Err(err))
+	}
+
+	// Like a PSP22 contract, report the allowance left after the transfer as well.
+	let allowance = <pallet_assets::Pallet<T> as AllowanceInspect<T::AccountId>>::allowance(
+		input.asset_id,
+		&input.from,
+		&spender,
+	);
+	deposit_event::<T, E>(
+		&mut env,
+		Psp22Event::Transfer {
+			asset_id: input.asset_id,
+			from: Some(input.from.clone()),
+			to: Some(input.to),
+			value: input.value,
+		},
+	)?;
+	deposit_event::<T, E>(
+		&mut env,
+		Psp22Event::Approval {
+			asset_id: input.asset_id,
+			owner: input.from,
+			spender,
+			value: allowance,
+		},
+	)?;
+
+	Ok(Ok(()))
+}
+
+#[derive(Debug, Clone, Copy)]
+enum AllowanceChange {
+	Set,
+	Increase,
+	Decrease,
+}
+
+/// Computes the allowance resulting from applying `change` with `value` to `current`.
+fn new_allowance<T: pallet_assets::Config>(
+	change: AllowanceChange,
+	current: T::Balance,
+	value: T::Balance,
+) -> Result<T::Balance, DispatchError> {
+	match change {
+		AllowanceChange::Set => Ok(value),
+		AllowanceChange::Increase =>
+			current.checked_add(&value).ok_or_else(|| ArithmeticError::Overflow.into()),
+		AllowanceChange::Decrease => current
+			.checked_sub(&value)
+			.ok_or_else(|| pallet_assets::Error::<T>::Unapproved.into()),
+	}
+}
+
+/// Moves the allowance of `spender` over the assets of `owner` from `current` to `value`.
+///
+/// Approvals of `pallet_assets` only ever accumulate, so an increase approves the difference
+/// on top of the existing approval. Any other change cancels the approval and approves `value`
+/// afresh, unless it is zero. Either way `pallet_assets` deposits its own approval events.
+fn set_allowance<T: pallet_assets::Config>(
+	asset_id: T::AssetId,
+	owner: &T::AccountId,
+	spender: &T::AccountId,
+	current: T::Balance,
+	value: T::Balance,
+) -> DispatchResult {
+	if value == current {
+		return Ok(())
+	}
+	if value > current {
+		return <pallet_assets::Pallet<T> as AllowanceMutate<T::AccountId>>::approve(
+			asset_id,
+			owner,
+			spender,
+			value - current,
+		)
+	}
+	<pallet_assets::Pallet<T>>::cancel_approval(
+		RawOrigin::Signed(owner.clone()).into(),
+		asset_id.into(),
+		T::Lookup::unlookup(spender.clone()),
+	)?;
+	if value.is_zero() {
+		return Ok(())
+	}
+	<pallet_assets::Pallet<T> as AllowanceMutate<T::AccountId>>::approve(
+		asset_id, owner, spender, value,
+	)
+}
+
+fn change_allowance<T, E>(
+	signer: Signer,
+	change: AllowanceChange,
+	env: Environment<E, InitState>,
+) -> Result<DispatchResult, DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	// Charged for the worst case: reading the allowance, cancelling it and approving anew.
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp22_change_allowance())?;
+	let input: Psp22ApproveInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
+	let owner = signer.account::<T, E>(&mut env);
+
+	let result = with_storage_layer(|| {
+		let current = <pallet_assets::Pallet<T> as AllowanceInspect<T::AccountId>>::allowance(
+			input.asset_id,
+			&owner,
+			&input.spender,
+		);
+		let value = new_allowance::<T>(change, current, input.value)?;
+		set_allowance::<T>(input.asset_id, &owner, &input.spender, current, value)?;
+		Ok(value)
+	});
+
+	match result {
+		Ok(value) => {
+			deposit_event::<T, E>(
+				&mut env,
+				Psp22Event::Approval {
+					asset_id: input.asset_id,
+					owner,
+					spender: input.spender,
+					value,
+				},
+			)?;
+			Ok(Ok(()))
+		},
+		Err(err) => Ok(Err(err)),
+	}
+}
+
+fn burn<T, E>(
+	signer: Signer,
+	env: Environment<E, InitState>,
+) -> Result<DispatchResult, DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp22_burn())?;
+	let input: Psp22BurnInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
+	let admin = signer.account::<T, E>(&mut env);
+
+	// `pallet_assets` burns at most the balance of the account, so report what was burned.
+	let balance = || {
+		<pallet_assets::Pallet<T> as Inspect<T::AccountId>>::balance(input.asset_id, &input.from)
+	};
+	let before = balance();
+	let result = <pallet_assets::Pallet<T>>::burn(
+		RawOrigin::Signed(admin).into(),
+		input.asset_id.into(),
+		T::Lookup::unlookup(input.from.clone()),
+		input.value,
+	);
+	if let Err(err) = result {
+		return Ok(Err(err))
+	}
+	let burned = before.saturating_sub(balance());
+
+	deposit_event::<T, E>(
+		&mut env,
+		Psp22Event::Transfer {
+			asset_id: input.asset_id,
+			from: Some(input.from.clone()),
+			to: None,
+			value: burned,
+		},
+	)?;
+
+	Ok(Ok(()))
+}
+
+fn mint<T, E>(
+	signer: Signer,
+	env: Environment<E, InitState>,
+) -> Result<DispatchResult, DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp22_mint())?;
+	let input: Psp22MintInput<T::AssetId, T::AccountId, T::Balance> = env.read_as()?;
+	let issuer = signer.account::<T, E>(&mut env);
+
+	let result = <pallet_assets::Pallet<T>>::mint(
+		RawOrigin::Signed(issuer).into(),
+		input.asset_id.into(),
+		T::Lookup::unlookup(input.to.clone()),
+		input.value,
+	);
+	if let Err(err) = result {
+		return Ok(Err(err))
+	}
+
+	deposit_event::<T, E>(
+		&mut env,
+		Psp22Event::Transfer {
+			asset_id: input.asset_id,
+			from: None,
+			to: Some(input.to),
+			value: input.value,
+		},
+	)?;
+
+	Ok(Ok(()))
+}
+
+/// Dispatches an asset management call signed by the calling contract, not by its caller.
+///
+/// This lets a contract create assets and act as their owner, admin, issuer or freezer, which
+/// `mint` and `burn` of [`Psp22StatusExtension`] then rely on. The deposits for the asset and its
+/// metadata are reserved from the contract's balance.
+fn manage<T, E>(
+	func_id: Management,
+	env: Environment<E, InitState>,
+) -> Result<DispatchResult, DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	let weight = match func_id {
+		Management::Create => ExtensionWeightInfo::<T>::psp22_create(),
+		Management::SetMetadata => ExtensionWeightInfo::<T>::psp22_set_metadata(),
+		Management::SetTeam => ExtensionWeightInfo::<T>::psp22_set_team(),
+		Management::TransferOwnership => ExtensionWeightInfo::<T>::psp22_transfer_ownership(),
+		Management::Freeze => ExtensionWeightInfo::<T>::psp22_freeze(),
+		Management::Thaw => ExtensionWeightInfo::<T>::psp22_thaw(),
+		Management::FreezeAsset => ExtensionWeightInfo::<T>::psp22_freeze_asset(),
+		Management::ThawAsset => ExtensionWeightInfo::<T>::psp22_thaw_asset(),
+		Management::StartDestroy => ExtensionWeightInfo::<T>::psp22_start_destroy(),
+	};
+	charge_weight::<T, E>(&mut env, weight)?;
+
+	let contract = env.ext().address().clone();
+	let origin =
+		|| -> <T as SysConfig>::RuntimeOrigin { RawOrigin::Signed(contract.clone()).into() };
+	let result = match func_id {
+		Management::Create => {
+			let input: Psp22CreateInput<T::AssetId, T::Balance> = env.read_as()?;
+			<pallet_assets::Pallet<T>>::create(
+				origin(),
+				input.asset_id.into(),
+				T::Lookup::unlookup(contract.clone()),
+				input.min_balance,
+			)
+		},
+		Management::SetMetadata => {
+			let len = env.in_len();
+			let input: Psp22SetMetadataInput<T::AssetId> = env.read_as_unbounded(len)?;
+			<pallet_assets::Pallet<T>>::set_metadata(
+				origin(),
+				input.asset_id.into(),
+				input.name,
+				input.symbol,
+				input.decimals,
+			)
+		},
+		Management::SetTeam => {
+			let input: Psp22SetTeamInput<T::AssetId, T::AccountId> = env.read_as()?;
+			<pallet_assets::Pallet<T>>::set_team(
+				origin(),
+				input.asset_id.into(),
+				T::Lookup::unlookup(input.issuer),
+				T::Lookup::unlookup(input.admin),
+				T::Lookup::unlookup(input.freezer),
+			)
+		},
+		Management::TransferOwnership => {
+			let input: Psp22TransferOwnershipInput<T::AssetId, T::AccountId> = env.read_as()?;
+			<pallet_assets::Pallet<T>>::transfer_ownership(
+				origin(),
+				input.asset_id.into(),
+				T::Lookup::unlookup(input.owner),
+			)
+		},
+		Management::Freeze => {
+			let input: Psp22FreezeInput<T::AssetId, T::AccountId> = env.read_as()?;
+			<pallet_assets::Pallet<T>>::freeze(
+				origin(),
+				input.asset_id.into(),
+				T::Lookup::unlookup(input.who),
+			)
+		},
+		Management::Thaw => {
+			let input: Psp22FreezeInput<T::AssetId, T::AccountId> = env.read_as()?;
+			<pallet_assets::Pallet<T>>::thaw(
+				origin(),
+				input.asset_id.into(),
+				T::Lookup::unlookup(input.who),
+			)
+		},
+		Management::FreezeAsset => {
+			let asset_id: T::AssetId = env.read_as()?;
+			<pallet_assets::Pallet<T>>::freeze_asset(origin(), asset_id.into())
+		},
+		Management::ThawAsset => {
+			let asset_id: T::AssetId = env.read_as()?;
+			<pallet_assets::Pallet<T>>::thaw_asset(origin(), asset_id.into())
+		},
+		Management::StartDestroy => {
+			let asset_id: T::AssetId = env.read_as()?;
+			<pallet_assets::Pallet<T>>::start_destroy(origin(), asset_id.into())
+		},
+	};
+
+	Ok(result)
+}
+
+/// Runs the function selected by the contract, returning the outcome of its runtime call.
+fn dispatch<T, E>(
+	signer: Signer,
+	env: Environment<E, InitState>,
+) -> Result<DispatchResult, DispatchError>
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let func_id = FuncId::try_from(env.func_id())?;
+	match func_id {
+		FuncId::Metadata(func_id) => metadata::<T, E>(func_id, env).map(Ok),
+		FuncId::Query(func_id) => query::<T, E>(func_id, env).map(Ok),
+		FuncId::Transfer => transfer::<T, E>(signer, env),
+		FuncId::TransferFrom => transfer_from::<T, E>(signer, env),
+		FuncId::Approve => change_allowance::<T, E>(signer, AllowanceChange::Set, env),
+		FuncId::IncreaseAllowance =>
+			change_allowance::<T, E>(signer, AllowanceChange::Increase, env),
+		FuncId::DecreaseAllowance =>
+			change_allowance::<T, E>(signer, AllowanceChange::Decrease, env),
+		FuncId::Burn => burn::<T, E>(signer, env),
+		FuncId::Mint => mint::<T, E>(signer, env),
+		FuncId::Management(func_id) => manage::<T, E>(func_id, env),
+	}
+}
+
+impl<T> ChainExtension<T> for Psp22Extension
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	fn call<E: Ext>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		dispatch::<T, E>(Signer::Caller, env)??;
+
+		Ok(RetVal::Converging(0))
+	}
+}
+
+impl<T> RegisteredChainExtension<T> for Psp22Extension
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	const ID: u16 = 0x0000;
+}
+
+impl<T> ChainExtension<T> for Psp22StatusExtension
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	fn call<E: Ext>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let result = dispatch::<T, E>(Signer::Contract, env)?;
+
+		Ok(RetVal::Converging(Psp22Error::status::<T>(result)))
+	}
+}
+
+impl<T> RegisteredChainExtension<T> for Psp22StatusExtension
+where
+	T: pallet_assets::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	const ID: u16 = 0x0003;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		chain_extension::test_utils::{call, deploy},
+		constants::currency::UNIT,
+		AccountId, Assets, Balance, Balances, Runtime, RuntimeEvent, RuntimeOrigin, System,
+	};
+	use frame_support::{
+		assert_ok,
+		traits::{fungibles::roles::Inspect as RolesInspect, GenesisBuild, ReservableCurrency},
+	};
+	use hex_literal::hex;
+	use sp_core::H256;
+
+	const ASSET: u32 = 1;
+	const OWNER: AccountId = AccountId::new([1; 32]);
+	const SPENDER: AccountId = AccountId::new([2; 32]);
+
+	const TRANSFER: u16 = 0xdb20;
+	const TRANSFER_FROM: u16 = 0x54b3;
+	const BURN: u16 = 0x9e55;
+	const MINT: u16 = 0x6bba;
+	const CREATE: u16 = 0x0001;
+	const APPROVE: u16 = 0xb20f;
+	const INCREASE_ALLOWANCE: u16 = 0x96d6;
+	const DECREASE_ALLOWANCE: u16 = 0xfecb;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let mut storage =
+			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances: vec![(OWNER, 1_000 * UNIT)] }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| {
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				ASSET.into(),
+				OWNER.into(),
+				true,
+				1,
+			));
+			System::set_block_number(1);
+		});
+		ext
+	}
+
+	fn allowance(owner: &AccountId) -> Balance {
+		<Assets as AllowanceInspect<AccountId>>::allowance(ASSET, owner, &SPENDER)
+	}
+
+	fn owner(asset: u32) -> Option<AccountId> {
+		<Assets as RolesInspect<AccountId>>::owner(asset)
+	}
+
+	/// Has the contract change the allowance of `SPENDER` over its own assets, returning the
+	/// status.
+	fn change(contract: &AccountId, func_id: u16, value: Balance) -> u32 {
+		call(&OWNER, contract, 3, func_id, (ASSET, SPENDER, value)).unwrap().status
+	}
+
+	#[test]
+	fn approve_sets_allowance() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, 10 * UNIT);
+			assert_eq!(change(&contract, APPROVE, 100), 0);
+			assert_eq!(allowance(&contract), 100);
+			assert_eq!(change(&contract, APPROVE, 150), 0);
+			assert_eq!(allowance(&contract), 150);
+			assert_eq!(change(&contract, APPROVE, 40), 0);
+			assert_eq!(allowance(&contract), 40);
+			assert_eq!(change(&contract, APPROVE, 0), 0);
+			assert_eq!(allowance(&contract), 0);
+		});
+	}
+
+	#[test]
+	fn increase_and_decrease_allowance() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, 10 * UNIT);
+			assert_eq!(change(&contract, INCREASE_ALLOWANCE, 100), 0);
+			assert_eq!(allowance(&contract), 100);
+			assert_eq!(change(&contract, INCREASE_ALLOWANCE, 50), 0);
+			assert_eq!(allowance(&contract), 150);
+			assert_eq!(change(&contract, DECREASE_ALLOWANCE, 120), 0);
+			assert_eq!(allowance(&contract), 30);
+			assert_eq!(change(&contract, APPROVE, 70), 0);
+			assert_eq!(change(&contract, DECREASE_ALLOWANCE, 70), 0);
+			assert_eq!(allowance(&contract), 0);
+		});
+	}
+
+	#[test]
+	fn decrease_below_zero_fails() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, 10 * UNIT);
+			assert_eq!(change(&contract, APPROVE, 10), 0);
+			assert_eq!(
+				change(&contract, DECREASE_ALLOWANCE, 11),
+				Psp22Error::InsufficientAllowance as u32
+			);
+			assert_eq!(allowance(&contract), 10);
+		});
+	}
+
+	#[test]
+	fn increase_overflow_fails() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, 10 * UNIT);
+			assert_eq!(change(&contract, APPROVE, 10), 0);
+			assert_eq!(
+				change(&contract, INCREASE_ALLOWANCE, Balance::MAX),
+				Psp22Error::Arithmetic as u32
+			);
+			assert_eq!(allowance(&contract), 10);
+		});
+	}
+
+	#[test]
+	fn allowance_changes_keep_approval_deposit() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, 10 * UNIT);
+			let reserved = || Balances::reserved_balance(&contract);
+			let before = reserved();
+			assert_eq!(change(&contract, APPROVE, 10), 0);
+			let deposit = reserved() - before;
+			assert!(deposit > 0);
+
+			assert_eq!(change(&contract, INCREASE_ALLOWANCE, 10), 0);
+			assert_eq!(change(&contract, APPROVE, 100), 0);
+			assert_eq!(change(&contract, DECREASE_ALLOWANCE, 30), 0);
+			assert_eq!(change(&contract, APPROVE, 50), 0);
+			assert_eq!(allowance(&contract), 50);
+			assert_eq!(reserved(), before + deposit);
+
+			// Only dropping the allowance to zero releases the deposit.
+			assert_eq!(change(&contract, DECREASE_ALLOWANCE, 50), 0);
+			assert_eq!(reserved(), before);
+		});
+	}
+
+	#[test]
+	fn allowance_changes_deposit_pallet_events() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, 10 * UNIT);
+			let approvals = || {
+				System::events()
+					.into_iter()
+					.filter_map(|record| match record.event {
+						RuntimeEvent::Assets(event) => Some(event),
+						_ => None,
+					})
+					.filter(|event| {
+						matches!(
+							event,
+							pallet_assets::Event::ApprovedTransfer { .. } |
+								pallet_assets::Event::ApprovalCancelled { .. }
+						)
+					})
+					.collect::<Vec<_>>()
+			};
+			let approved = |amount| pallet_assets::Event::ApprovedTransfer {
+				asset_id: ASSET,
+				source: contract.clone(),
+				delegate: SPENDER,
+				amount,
+			};
+			let cancelled = pallet_assets::Event::ApprovalCancelled {
+				asset_id: ASSET,
+				owner: contract.clone(),
+				delegate: SPENDER,
+			};
+
+			assert_eq!(change(&contract, APPROVE, 100), 0);
+			assert_eq!(change(&contract, INCREASE_ALLOWANCE, 20), 0);
+			assert_eq!(change(&contract, DECREASE_ALLOWANCE, 50), 0);
+			assert_eq!(change(&contract, APPROVE, 0), 0);
+			assert_eq!(
+				approvals(),
+				vec![approved(100), approved(20), cancelled.clone(), approved(70), cancelled]
+			);
+		});
+	}
+
+	#[test]
+	fn topics_are_encoded_like_ink() {
+		// The topics of `Transfer { from: Some(OWNER), to: None, value: 100 }` emitted by the
+		// ink! ERC-20 example contract.
+		assert_eq!(
+			topic::<Runtime>(b"", b"Erc20::Transfer"),
+			H256(hex!("45726332303a3a5472616e736665720000000000000000000000000000000000")),
+		);
+		assert_eq!(
+			topic::<Runtime>(b"Erc20::Transfer::from", &Some(OWNER)),
+			H256(hex!("a2593c03ce78a44d7b95e980f10dca3196af1259109b353405da3a69c2732879")),
+		);
+		assert_eq!(
+			topic::<Runtime>(b"Erc20::Transfer::to", &None::<AccountId>),
+			H256(hex!("45726332303a3a5472616e736665723a3a746f00000000000000000000000000")),
+		);
+		assert_eq!(
+			topic::<Runtime>(b"Erc20::Transfer::value", &(100 as Balance)),
+			H256(hex!("01bd6c3e5e3bcebc12337e266ffe8727964d1ba4bbab404ec7c062ccfccbdb87")),
+		);
+
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, UNIT);
+			assert_ok!(Assets::mint(
+				RuntimeOrigin::signed(OWNER),
+				ASSET.into(),
+				contract.clone().into(),
+				100,
+			));
+			let input = (ASSET, SPENDER, 10 as Balance);
+			assert_eq!(call(&OWNER, &contract, 3, TRANSFER, input).unwrap().status, 0);
+
+			let record = System::events()
+				.into_iter()
+				.rev()
+				.find(|record| {
+					matches!(
+						record.event,
+						RuntimeEvent::Contracts(pallet_contracts::Event::ContractEmitted { .. })
+					)
+				})
+				.unwrap();
+			assert_eq!(
+				record.topics,
+				vec![
+					topic::<Runtime>(b"", b"PSP22::Transfer"),
+					topic::<Runtime>(b"PSP22::Transfer::asset_id", &ASSET),
+					topic::<Runtime>(b"PSP22::Transfer::from", &Some(&contract)),
+					topic::<Runtime>(b"PSP22::Transfer::to", &Some(SPENDER)),
+				]
+			);
+			// Short values are not hashed, so the event path can be read back from the topic.
+			assert_eq!(&record.topics[0][..15], b"PSP22::Transfer");
+		});
+	}
+
+	#[test]
+	fn contract_administers_the_assets_it_creates() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, 200 * UNIT);
+			let asset = ASSET + 1;
+			let balance = || <Assets as Inspect<AccountId>>::balance(asset, &SPENDER);
+
+			let input = (asset, 1 as Balance);
+			assert_eq!(call(&OWNER, &contract, 3, CREATE, input).unwrap().status, 0);
+			assert_eq!(owner(asset), Some(contract.clone()));
+			let input = (asset, SPENDER, 100 as Balance);
+			assert_eq!(call(&OWNER, &contract, 3, MINT, input).unwrap().status, 0);
+			assert_eq!(balance(), 100);
+			let input = (asset, SPENDER, 40 as Balance);
+			assert_eq!(call(&OWNER, &contract, 3, BURN, input).unwrap().status, 0);
+			assert_eq!(balance(), 60);
+
+			// The caller of the contract holds no role on the asset of the contract.
+			assert_eq!(owner(ASSET), Some(OWNER));
+			let input = (ASSET, SPENDER, 100 as Balance);
+			assert_eq!(
+				call(&OWNER, &contract, 3, MINT, input).unwrap().status,
+				Psp22Error::NoPermission as u32
+			);
+		});
+	}
+
+	#[test]
+	fn legacy_extension_mints_and_burns_as_the_caller() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, UNIT);
+			let balance = || <Assets as Inspect<AccountId>>::balance(ASSET, &SPENDER);
+
+			// `OWNER` is the issuer and admin of `ASSET`, while the contract is neither.
+			let input = (ASSET, SPENDER, 100 as Balance);
+			assert_eq!(call(&OWNER, &contract, 0, MINT, input).unwrap().status, 0);
+			let input = (ASSET, SPENDER, 40 as Balance);
+			assert_eq!(call(&OWNER, &contract, 0, BURN, input).unwrap().status, 0);
+			assert_eq!(balance(), 60);
+			assert_eq!(
+				call(&OWNER, &contract, 3, MINT, input).unwrap().status,
+				Psp22Error::NoPermission as u32
+			);
+		});
+	}
+
+	#[test]
+	fn legacy_extension_acts_for_the_caller() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, UNIT);
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(OWNER), ASSET.into(), OWNER.into(), 100));
+			let balance = |who| <Assets as Inspect<AccountId>>::balance(ASSET, who);
+
+			let input = (ASSET, SPENDER, 30 as Balance);
+			assert_eq!(call(&OWNER, &contract, 0, TRANSFER, input).unwrap().status, 0);
+			assert_eq!(call(&OWNER, &contract, 0, APPROVE, input).unwrap().status, 0);
+			assert_eq!(balance(&OWNER), 70);
+			assert_eq!(allowance(&OWNER), 30);
+		});
+	}
+
+	#[test]
+	fn status_extension_cannot_move_assets_of_the_caller() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, UNIT);
+			assert_ok!(Assets::mint(RuntimeOrigin::signed(OWNER), ASSET.into(), OWNER.into(), 100));
+			let balance = |who| <Assets as Inspect<AccountId>>::balance(ASSET, who);
+
+			let input = (ASSET, SPENDER, 30 as Balance);
+			assert_eq!(
+				call(&OWNER, &contract, 3, TRANSFER, input).unwrap().status,
+				Psp22Error::InsufficientBalance as u32
+			);
+			assert_eq!(balance(&OWNER), 100);
+
+			// Approving the contract lets it spend the assets of `OWNER`, and only that much.
+			assert_ok!(Assets::approve_transfer(
+				RuntimeOrigin::signed(OWNER),
+				ASSET.into(),
+				contract.clone().into(),
+				30,
+			));
+			let input = (ASSET, OWNER, SPENDER, 30 as Balance);
+			assert_eq!(call(&OWNER, &contract, 3, TRANSFER_FROM, input).unwrap().status, 0);
+			assert_eq!(balance(&SPENDER), 30);
+			assert_eq!(
+				call(&OWNER, &contract, 3, TRANSFER_FROM, input).unwrap().status,
+				Psp22Error::InsufficientAllowance as u32
+			);
+		});
+	}
+
+	#[test]
+	fn status_codes_need_the_status_extension() {
+		new_test_ext().execute_with(|| {
+			let contract = deploy(&OWNER, UNIT);
+			// `OWNER` holds none of the asset.
+			let input = (ASSET, SPENDER, 10 as Balance);
+
+			assert!(call(&OWNER, &contract, 0, TRANSFER, input).is_err());
+			assert_eq!(
+				call(&OWNER, &contract, 3, TRANSFER, input).unwrap().status,
+				Psp22Error::InsufficientBalance as u32
+			);
+		});
+	}
+}
@@ -0,0 +1,453 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::RawOrigin,
+	traits::{
+		tokens::nonfungibles_v2::{Create, Inspect, InspectEnumerable},
+		Get,
+	},
+	BoundedVec,
+};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RegisteredChainExtension, RetVal, SysConfig,
+};
+use pallet_nfts::{AttributeNamespace, CollectionConfig, CollectionSettings, MintSettings};
+use sp_core::crypto::UncheckedFrom;
+use sp_runtime::{traits::StaticLookup, DispatchError};
+
+use super::{
+	charge_weight, convert_err, return_per_byte, weights::WeightInfo, ExtensionWeightInfo,
+};
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp34CreateCollectionInput<AccountId> {
+	admin: AccountId,
+	max_supply: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp34ItemInput<CollectionId, ItemId> {
+	collection: CollectionId,
+	item: ItemId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp34BalanceOfInput<CollectionId, AccountId> {
+	collection: CollectionId,
+	owner: AccountId,
+}
+
+#[derive(Encode, Decode, MaxEncodedLen)]
+struct Psp34GetAttributeInput<CollectionId, ItemId, KeyLimit: Get<u32>> {
+	collection: CollectionId,
+	item: ItemId,
+	key: BoundedVec<u8, KeyLimit>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp34MintInput<CollectionId, ItemId, AccountId> {
+	collection: CollectionId,
+	item: ItemId,
+	to: AccountId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp34TransferInput<CollectionId, ItemId, AccountId> {
+	collection: CollectionId,
+	item: ItemId,
+	to: AccountId,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode, MaxEncodedLen)]
+struct Psp34ApproveInput<CollectionId, ItemId, AccountId> {
+	collection: CollectionId,
+	item: ItemId,
+	operator: AccountId,
+	approved: bool,
+}
+
+#[derive(Encode, Decode, MaxEncodedLen)]
+struct Psp34SetAttributeInput<CollectionId, ItemId, KeyLimit: Get<u32>, ValueLimit: Get<u32>> {
+	collection: CollectionId,
+	item: ItemId,
+	key: BoundedVec<u8, KeyLimit>,
+	value: BoundedVec<u8, ValueLimit>,
+}
+
+/// Bridges ink! PSP34 contracts to `pallet_nfts`.
+///
+/// State changing calls are signed by the contract, so it can only move the items it owns or was
+/// approved for.
+#[derive(Default)]
+pub struct Psp34Extension;
+
+enum FuncId {
+	CreateCollection,
+	Query(Query),
+	Mint,
+	Burn,
+	Transfer,
+	Approve,
+	SetAttribute,
+}
+
+#[derive(Debug)]
+enum Query {
+	OwnerOf,
+	BalanceOf,
+	GetAttribute,
+}
+
+impl TryFrom<u16> for FuncId {
+	type Error = DispatchError;
+
+	fn try_from(func_id: u16) -> Result<Self, Self::Error> {
+		let id = match func_id {
+			// Note: As for PSP22, the first two bytes of the PSP34 interface selectors are used
+			// as function IDs. Collection creation and attribute writes have no PSP34 selector.
+			0x0001 => Self::CreateCollection,
+			0x0002 => Self::SetAttribute,
+			0x9972 => Self::Query(Query::OwnerOf),
+			0xcde7 => Self::Query(Query::BalanceOf),
+			0xf19d => Self::Query(Query::GetAttribute),
+			0x6c41 => Self::Mint,
+			0x63c9 => Self::Burn,
+			0x3128 => Self::Transfer,
+			0x1932 => Self::Approve,
+			_ => return Err(DispatchError::Other("Unimplemented func_id")),
+		};
+
+		Ok(id)
+	}
+}
+
+fn create_collection<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp34_create_collection())?;
+	let input: Psp34CreateCollectionInput<T::AccountId> = env.read_as()?;
+	let owner = env.ext().address().clone();
+
+	let config = CollectionConfig {
+		settings: CollectionSettings::all_enabled(),
+		max_supply: input.max_supply,
+		mint_settings: MintSettings::default(),
+	};
+	let collection = <pallet_nfts::Pallet<T> as Create<T::AccountId, _>>::create_collection(
+		&owner,
+		&input.admin,
+		&config,
+	)
+	.map_err(convert_err("ChainExtension failed to call create_collection"))?;
+
+	env.write(&collection.encode(), false, Some(return_per_byte::<T>()))
+		.map_err(convert_err("ChainExtension failed to call create_collection"))
+}
+
+fn query<T, E>(func_id: Query, env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	let weight = match func_id {
+		Query::BalanceOf => ExtensionWeightInfo::<T>::psp34_balance_of(0),
+		Query::OwnerOf | Query::GetAttribute => ExtensionWeightInfo::<T>::psp34_query(),
+	};
+	charge_weight::<T, E>(&mut env, weight)?;
+	let result = match func_id {
+		Query::OwnerOf => {
+			let input: Psp34ItemInput<T::CollectionId, T::ItemId> = env.read_as()?;
+			<pallet_nfts::Pallet<T> as Inspect<T::AccountId>>::owner(&input.collection, &input.item)
+				.encode()
+		},
+		Query::BalanceOf => {
+			let input: Psp34BalanceOfInput<T::CollectionId, T::AccountId> = env.read_as()?;
+			// `pallet_nfts` keeps no count of the items of an account, so they are iterated.
+			// Every item found is charged as it is read, which bounds the loop by the gas limit.
+			let per_item = ExtensionWeightInfo::<T>::psp34_balance_of(1)
+				.saturating_sub(ExtensionWeightInfo::<T>::psp34_balance_of(0));
+			let items =
+				<pallet_nfts::Pallet<T> as InspectEnumerable<T::AccountId>>::owned_in_collection(
+					&input.collection,
+					&input.owner,
+				);
+			let mut balance: u32 = 0;
+			for _ in items {
+				env.charge_weight(per_item)?;
+				balance = balance.saturating_add(1);
+			}
+			balance.encode()
+		},
+		Query::GetAttribute => {
+			let input: Psp34GetAttributeInput<T::CollectionId, T::ItemId, T::KeyLimit> =
+				env.read_as()?;
+			<pallet_nfts::Pallet<T> as Inspect<T::AccountId>>::attribute(
+				&input.collection,
+				&input.item,
+				&input.key,
+			)
+			.encode()
+		},
+	};
+
+	env.write(&result, false, Some(return_per_byte::<T>()))
+		.map_err(convert_err("ChainExtension failed to call PSP34 query"))
+}
+
+fn mint<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp34_mint())?;
+	let input: Psp34MintInput<T::CollectionId, T::ItemId, T::AccountId> = env.read_as()?;
+	let contract = env.ext().address();
+
+	<pallet_nfts::Pallet<T>>::mint(
+		RawOrigin::Signed(contract.clone()).into(),
+		input.collection,
+		input.item,
+		T::Lookup::unlookup(input.to),
+		None,
+	)
+	.map_err(convert_err("ChainExtension failed to call mint"))
+}
+
+fn burn<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp34_burn())?;
+	let input: Psp34ItemInput<T::CollectionId, T::ItemId> = env.read_as()?;
+	let contract = env.ext().address();
+
+	<pallet_nfts::Pallet<T>>::burn(
+		RawOrigin::Signed(contract.clone()).into(),
+		input.collection,
+		input.item,
+		None,
+	)
+	.map_err(convert_err("ChainExtension failed to call burn"))
+}
+
+fn transfer<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp34_transfer())?;
+	let input: Psp34TransferInput<T::CollectionId, T::ItemId, T::AccountId> = env.read_as()?;
+	let contract = env.ext().address();
+
+	<pallet_nfts::Pallet<T>>::transfer(
+		RawOrigin::Signed(contract.clone()).into(),
+		input.collection,
+		input.item,
+		T::Lookup::unlookup(input.to),
+	)
+	.map_err(convert_err("ChainExtension failed to call transfer"))
+}
+
+fn approve<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	// Charged before the input tells whether the approval is granted or cancelled.
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp34_approve())?;
+	let input: Psp34ApproveInput<T::CollectionId, T::ItemId, T::AccountId> = env.read_as()?;
+	let origin = RawOrigin::Signed(env.ext().address().clone()).into();
+	let operator = T::Lookup::unlookup(input.operator);
+
+	if input.approved {
+		<pallet_nfts::Pallet<T>>::approve_transfer(
+			origin,
+			input.collection,
+			input.item,
+			operator,
+			None,
+		)
+	} else {
+		<pallet_nfts::Pallet<T>>::cancel_approval(origin, input.collection, input.item, operator)
+	}
+	.map_err(convert_err("ChainExtension failed to call approve"))
+}
+
+fn set_attribute<T, E>(env: Environment<E, InitState>) -> Result<(), DispatchError>
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	E: Ext<T = T>,
+{
+	let mut env = env.buf_in_buf_out();
+	charge_weight::<T, E>(&mut env, ExtensionWeightInfo::<T>::psp34_set_attribute())?;
+	// Keys and values longer than the limits of `pallet_nfts` fail to decode.
+	let input: Psp34SetAttributeInput<T::CollectionId, T::ItemId, T::KeyLimit, T::ValueLimit> =
+		env.read_as()?;
+	let contract = env.ext().address();
+
+	<pallet_nfts::Pallet<T>>::set_attribute(
+		RawOrigin::Signed(contract.clone()).into(),
+		input.collection,
+		Some(input.item),
+		AttributeNamespace::CollectionOwner,
+		input.key,
+		input.value,
+	)
+	.map_err(convert_err("ChainExtension failed to call set_attribute"))
+}
+
+impl<T> ChainExtension<T> for Psp34Extension
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	fn call<E: Ext>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let func_id = FuncId::try_from(env.func_id())?;
+		match func_id {
+			FuncId::CreateCollection => create_collection::<T, E>(env)?,
+			FuncId::Query(func_id) => query::<T, E>(func_id, env)?,
+			FuncId::Mint => mint::<T, E>(env)?,
+			FuncId::Burn => burn::<T, E>(env)?,
+			FuncId::Transfer => transfer::<T, E>(env)?,
+			FuncId::Approve => approve::<T, E>(env)?,
+			FuncId::SetAttribute => set_attribute::<T, E>(env)?,
+		}
+
+		Ok(RetVal::Converging(0))
+	}
+}
+
+impl<T> RegisteredChainExtension<T> for Psp34Extension
+where
+	T: pallet_nfts::Config + pallet_contracts::Config,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	const ID: u16 = 0x0001;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		chain_extension::test_utils::{call, deploy, new_test_ext},
+		constants::currency::UNIT,
+		AccountId, Nfts,
+	};
+
+	const ALICE: AccountId = AccountId::new([1; 32]);
+	const BOB: AccountId = AccountId::new([2; 32]);
+
+	const CREATE_COLLECTION: u16 = 0x0001;
+	const SET_ATTRIBUTE: u16 = 0x0002;
+	const BALANCE_OF: u16 = 0xcde7;
+	const GET_ATTRIBUTE: u16 = 0xf19d;
+	const MINT: u16 = 0x6c41;
+	const BURN: u16 = 0x63c9;
+	const TRANSFER: u16 = 0x3128;
+	const APPROVE: u16 = 0x1932;
+
+	/// Deploys the contract and has it create and administer a collection.
+	fn setup() -> (AccountId, u32) {
+		let contract = deploy(&ALICE, 500 * UNIT);
+		let output = call(&ALICE, &contract, 1, CREATE_COLLECTION, (&contract, None::<u32>))
+			.unwrap()
+			.output;
+		(contract, u32::decode(&mut &output[..]).unwrap())
+	}
+
+	fn mint(contract: &AccountId, collection: u32, item: u32, to: &AccountId) {
+		let result = call(&ALICE, contract, 1, MINT, (collection, item, to)).unwrap();
+		assert_eq!(result.status, 0);
+	}
+
+	fn mint_to_bob(contract: &AccountId, collection: u32, item: u32) {
+		mint(contract, collection, item, &BOB);
+	}
+
+	fn balance_of(contract: &AccountId, collection: u32) -> (u32, u64) {
+		let result = call(&ALICE, contract, 1, BALANCE_OF, (collection, BOB)).unwrap();
+		(u32::decode(&mut &result.output[..]).unwrap(), result.gas_consumed)
+	}
+
+	#[test]
+	fn contracts_cannot_move_items_of_their_caller() {
+		new_test_ext(vec![(ALICE, 1_000 * UNIT)]).execute_with(|| {
+			let (contract, collection) = setup();
+			let owner = |item| <Nfts as Inspect<AccountId>>::owner(&collection, &item);
+			let collection_owner = <Nfts as Inspect<AccountId>>::collection_owner(&collection);
+			assert_eq!(collection_owner, Some(contract.clone()));
+			mint(&contract, collection, 0, &ALICE);
+			mint(&contract, collection, 1, &contract);
+
+			// Calling the contract does not let it act as `ALICE`.
+			assert!(call(&ALICE, &contract, 1, TRANSFER, (collection, 0u32, BOB)).is_err());
+			assert!(call(&ALICE, &contract, 1, APPROVE, (collection, 0u32, BOB, true)).is_err());
+			assert!(call(&ALICE, &contract, 1, BURN, (collection, 0u32)).is_err());
+			assert_eq!(owner(0), Some(ALICE));
+
+			let result = call(&ALICE, &contract, 1, TRANSFER, (collection, 1u32, BOB)).unwrap();
+			assert_eq!(result.status, 0);
+			assert_eq!(owner(1), Some(BOB));
+		});
+	}
+
+	#[test]
+	fn balance_of_charges_per_item() {
+		new_test_ext(vec![(ALICE, 1_000 * UNIT)]).execute_with(|| {
+			let (contract, collection) = setup();
+			let (balance, empty) = balance_of(&contract, collection);
+			assert_eq!(balance, 0);
+
+			for item in 0..3 {
+				mint_to_bob(&contract, collection, item);
+			}
+			assert_eq!(<Nfts as Inspect<AccountId>>::owner(&collection, &0), Some(BOB));
+			let (balance, three) = balance_of(&contract, collection);
+			assert_eq!(balance, 3);
+			assert!(three > empty);
+		});
+	}
+
+	#[test]
+	fn attribute_keys_are_bounded() {
+		new_test_ext(vec![(ALICE, 1_000 * UNIT)]).execute_with(|| {
+			let (contract, collection) = setup();
+			mint_to_bob(&contract, collection, 0);
+
+			let key = b"color".to_vec();
+			let value = b"blue".to_vec();
+			let input = (collection, 0u32, &key, &value);
+			assert_eq!(call(&ALICE, &contract, 1, SET_ATTRIBUTE, input).unwrap().status, 0);
+			let output = call(&ALICE, &contract, 1, GET_ATTRIBUTE, (collection, 0u32, &key))
+				.unwrap()
+				.output;
+			assert_eq!(Option::<Vec<u8>>::decode(&mut &output[..]).unwrap(), Some(value.clone()));
+
+			// One byte over `KeyLimit` traps the contract instead of being decoded.
+			let long_key = vec![0u8; 33];
+			let input = (collection, 0u32, &long_key, &value);
+			assert!(call(&ALICE, &contract, 1, SET_ATTRIBUTE, input).is_err());
+			let input = (collection, 0u32, &long_key);
+			assert!(call(&ALICE, &contract, 1, GET_ATTRIBUTE, input).is_err());
+		});
+	}
+}
@@ -0,0 +1,140 @@
+//! Weights of the chain extension functions.
+//!
+//! Regenerate them from the benchmarks in `benchmarking.rs` with
+//!
+//! ```sh
+//! ./target/release/lunes-node benchmark pallet --chain dev --pallet chain_extension \
+//! 	--extrinsic '*' --steps 50 --repeat 20 --output runtime/src/chain_extension/weights.rs
+//! ```
+//!
+//! Until then, every function weighs as much as the pallet call it wraps plus the storage reads
+//! it adds, and the forwarding contract costs nothing on top of that.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use pallet_assets::{weights::SubstrateWeight as AssetsWeight, WeightInfo as _};
+use pallet_balances::{weights::SubstrateWeight as BalancesWeight, WeightInfo as _};
+use pallet_nfts::{weights::SubstrateWeight as NftsWeight, WeightInfo as _};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for the chain extension.
+pub trait WeightInfo {
+	fn forward() -> Weight;
+	fn psp22_metadata() -> Weight;
+	fn psp22_query() -> Weight;
+	fn psp22_transfer() -> Weight;
+	fn psp22_transfer_from() -> Weight;
+	fn psp22_change_allowance() -> Weight;
+	fn psp22_burn() -> Weight;
+	fn psp22_mint() -> Weight;
+	fn psp22_create() -> Weight;
+	fn psp22_set_metadata() -> Weight;
+	fn psp22_set_team() -> Weight;
+	fn psp22_transfer_ownership() -> Weight;
+	fn psp22_freeze() -> Weight;
+	fn psp22_thaw() -> Weight;
+	fn psp22_freeze_asset() -> Weight;
+	fn psp22_thaw_asset() -> Weight;
+	fn psp22_start_destroy() -> Weight;
+	fn psp34_create_collection() -> Weight;
+	fn psp34_query() -> Weight;
+	fn psp34_balance_of(n: u32) -> Weight;
+	fn psp34_mint() -> Weight;
+	fn psp34_burn() -> Weight;
+	fn psp34_transfer() -> Weight;
+	fn psp34_approve() -> Weight;
+	fn psp34_set_attribute() -> Weight;
+	fn native_query() -> Weight;
+	fn native_transfer() -> Weight;
+}
+
+/// Weights for the chain extension using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn forward() -> Weight {
+		Weight::zero()
+	}
+	fn psp22_metadata() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn psp22_query() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn psp22_transfer() -> Weight {
+		AssetsWeight::<T>::transfer_keep_alive()
+	}
+	fn psp22_transfer_from() -> Weight {
+		AssetsWeight::<T>::transfer_approved().saturating_add(T::DbWeight::get().reads(1))
+	}
+	fn psp22_change_allowance() -> Weight {
+		AssetsWeight::<T>::cancel_approval()
+			.saturating_add(AssetsWeight::<T>::approve_transfer())
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	fn psp22_burn() -> Weight {
+		AssetsWeight::<T>::burn().saturating_add(T::DbWeight::get().reads(1))
+	}
+	fn psp22_mint() -> Weight {
+		AssetsWeight::<T>::mint()
+	}
+	fn psp22_create() -> Weight {
+		AssetsWeight::<T>::create()
+	}
+	fn psp22_set_metadata() -> Weight {
+		// `pallet_assets` limits names and symbols to the `StringLimit` of 50 bytes.
+		AssetsWeight::<T>::set_metadata(50, 50)
+	}
+	fn psp22_set_team() -> Weight {
+		AssetsWeight::<T>::set_team()
+	}
+	fn psp22_transfer_ownership() -> Weight {
+		AssetsWeight::<T>::transfer_ownership()
+	}
+	fn psp22_freeze() -> Weight {
+		AssetsWeight::<T>::freeze()
+	}
+	fn psp22_thaw() -> Weight {
+		AssetsWeight::<T>::thaw()
+	}
+	fn psp22_freeze_asset() -> Weight {
+		AssetsWeight::<T>::freeze_asset()
+	}
+	fn psp22_thaw_asset() -> Weight {
+		AssetsWeight::<T>::thaw_asset()
+	}
+	fn psp22_start_destroy() -> Weight {
+		AssetsWeight::<T>::start_destroy()
+	}
+	fn psp34_create_collection() -> Weight {
+		NftsWeight::<T>::create()
+	}
+	fn psp34_query() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn psp34_balance_of(n: u32) -> Weight {
+		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().reads(n.into()))
+	}
+	fn psp34_mint() -> Weight {
+		NftsWeight::<T>::mint()
+	}
+	fn psp34_burn() -> Weight {
+		NftsWeight::<T>::burn()
+	}
+	fn psp34_transfer() -> Weight {
+		NftsWeight::<T>::transfer()
+	}
+	fn psp34_approve() -> Weight {
+		NftsWeight::<T>::approve_transfer().max(NftsWeight::<T>::cancel_approval())
+	}
+	fn psp34_set_attribute() -> Weight {
+		NftsWeight::<T>::set_attribute()
+	}
+	fn native_query() -> Weight {
+		T::DbWeight::get().reads(1)
+	}
+	fn native_transfer() -> Weight {
+		BalancesWeight::<T>::transfer_keep_alive()
+	}
+}
@@ -11,6 +11,8 @@ pub mod currency {
 
 	pub const MIN_VALIDATOR_BOND: Balance = 50_000 * UNIT;
     pub const MIN_NOMINATOR_BOND: Balance = 100 * UNIT;
+	pub const MIN_POOL_JOIN_BOND: Balance = UNIT;
+	pub const MIN_POOL_CREATE_BOND: Balance = MIN_NOMINATOR_BOND;
 
     pub const TOTAL_INITIAL_ISSUANCE_LUNES: Balance = 200_000_000 * UNIT;
 	pub const TOTAL_INITIAL_ISSUANCE_LUNES_TEST: Balance = 10_000_000_000 * UNIT;
@@ -23,6 +25,27 @@ pub mod currency {
 	}
 }
 
+/// Limits of the nomination pools.
+pub mod pools {
+	pub const MAX_POOLS: u32 = 16;
+	pub const MAX_MEMBERS_PER_POOL: u32 = 32;
+	pub const MAX_MEMBERS: u32 = MAX_POOLS * MAX_MEMBERS_PER_POOL;
+}
+
+/// EIP-155 chain ids and decimals of the EVM.
+pub mod evm {
+	use node_primitives::Balance;
+
+	/// Chain id of the EVM on the main network.
+	pub const CHAIN_ID: u64 = 3_327;
+	/// Chain id of the EVM on test and development networks.
+	pub const TESTNET_CHAIN_ID: u64 = 3_328;
+	/// Decimals of LUNES in the EVM, those of ether.
+	pub const DECIMALS: u32 = 18;
+	/// EVM units per smallest LUNES unit.
+	pub const SCALE: Balance = 10u128.pow(DECIMALS - super::currency::TOKEN_DECIMALS);
+}
+
 /// Time.
 pub mod time {
 	use node_primitives::{BlockNumber, Moment};
@@ -0,0 +1,441 @@
+//! EVM support: the gas price, the block author and the precompiles used by `pallet_evm`, the
+//! view of the LUNES balances it works with, and the default Ethereum address of a LUNES account.
+//!
+//! LUNES has 8 decimals on chain. The EVM sees balances with the 18 decimals of ether, which
+//! Ethereum wallets and Solidity contracts assume, through [`EvmBalances`].
+
+use crate::{
+	constants::evm::SCALE, AccountId, Aura, Balance, Balances, NegativeImbalance, TargetTransferFee,
+};
+use core::marker::PhantomData;
+use frame_support::{
+	traits::{
+		fungible::Inspect,
+		tokens::{DepositConsequence, WithdrawConsequence},
+		Currency, ExistenceRequirement, FindAuthor, Imbalance, OnUnbalanced, SameOrOther,
+		SignedImbalance, TryDrop, WithdrawReasons,
+	},
+	weights::Weight,
+};
+use pallet_evm::{FeeCalculator, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet};
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_simple::{ECRecover, Identity, Ripemd160, Sha256};
+use sp_core::{crypto::ByteArray, H160, U256};
+use sp_runtime::{traits::Convert, ConsensusEngineId, DispatchError, DispatchResult};
+
+/// Gas used by a plain value transfer.
+const TRANSFER_GAS: Balance = 21_000;
+
+/// Prices gas in LUNES, so that a plain EVM transfer costs about as much as a native one.
+pub struct LunesGasPrice;
+
+impl FeeCalculator for LunesGasPrice {
+	fn min_gas_price() -> (U256, Weight) {
+		(U256::from(TargetTransferFee::get() * SCALE / TRANSFER_GAS), Weight::zero())
+	}
+}
+
+/// The address a LUNES account gets when it claims its default address with
+/// `EvmAccounts::claim_default_address`: the first 20 bytes of the account.
+pub struct TruncatedAddress;
+
+impl Convert<AccountId, H160> for TruncatedAddress {
+	fn convert(account: AccountId) -> H160 {
+		H160::from_slice(&AsRef::<[u8; 32]>::as_ref(&account)[..20])
+	}
+}
+
+/// An EVM amount in LUNES, rounded down.
+fn to_lunes(amount: Balance) -> Balance {
+	amount / SCALE
+}
+
+/// An EVM amount in LUNES, rounded up.
+fn to_lunes_ceil(amount: Balance) -> Balance {
+	amount / SCALE + if amount % SCALE == 0 { 0 } else { 1 }
+}
+
+/// A LUNES amount in EVM units.
+fn to_evm(amount: Balance) -> Balance {
+	amount.saturating_mul(SCALE)
+}
+
+/// `Balances` with amounts in the 18 decimals of the EVM.
+///
+/// Accounts hold no fractions of the smallest LUNES unit. Amounts taken from an account or an
+/// imbalance are rounded up, and amounts credited or transferred are rounded down, so rounding
+/// never creates LUNES.
+pub struct EvmBalances;
+
+/// An imbalance of `Balances` that reports its amount in EVM units.
+pub struct EvmImbalance<I>(I);
+
+impl<I: Default> Default for EvmImbalance<I> {
+	fn default() -> Self {
+		Self(I::default())
+	}
+}
+
+impl<I: TryDrop> TryDrop for EvmImbalance<I> {
+	fn try_drop(self) -> Result<(), Self> {
+		self.0.try_drop().map_err(Self)
+	}
+}
+
+impl<I> Imbalance<Balance> for EvmImbalance<I>
+where
+	I: Imbalance<Balance>,
+	I::Opposite: Imbalance<Balance, Opposite = I>,
+{
+	type Opposite = EvmImbalance<I::Opposite>;
+
+	fn zero() -> Self {
+		Self(I::zero())
+	}
+
+	fn drop_zero(self) -> Result<(), Self> {
+		self.0.drop_zero().map_err(Self)
+	}
+
+	fn split(self, amount: Balance) -> (Self, Self) {
+		let (first, second) = self.0.split(to_lunes_ceil(amount));
+		(Self(first), Self(second))
+	}
+
+	fn merge(self, other: Self) -> Self {
+		Self(self.0.merge(other.0))
+	}
+
+	fn subsume(&mut self, other: Self) {
+		self.0.subsume(other.0)
+	}
+
+	fn offset(self, other: Self::Opposite) -> SameOrOther<Self, Self::Opposite> {
+		match self.0.offset(other.0) {
+			SameOrOther::None => SameOrOther::None,
+			SameOrOther::Same(same) => SameOrOther::Same(Self(same)),
+			SameOrOther::Other(other) => SameOrOther::Other(EvmImbalance(other)),
+		}
+	}
+
+	fn peek(&self) -> Balance {
+		to_evm(self.0.peek())
+	}
+}
+
+type PositiveImbalance = <Balances as Currency<AccountId>>::PositiveImbalance;
+
+impl Currency<AccountId> for EvmBalances {
+	type Balance = Balance;
+	type PositiveImbalance = EvmImbalance<PositiveImbalance>;
+	type NegativeImbalance = EvmImbalance<NegativeImbalance>;
+
+	fn total_balance(who: &AccountId) -> Balance {
+		to_evm(Balances::total_balance(who))
+	}
+
+	fn can_slash(who: &AccountId, value: Balance) -> bool {
+		Balances::can_slash(who, to_lunes_ceil(value))
+	}
+
+	fn total_issuance() -> Balance {
+		to_evm(<Balances as Currency<AccountId>>::total_issuance())
+	}
+
+	fn minimum_balance() -> Balance {
+		to_evm(<Balances as Currency<AccountId>>::minimum_balance())
+	}
+
+	fn burn(amount: Balance) -> Self::PositiveImbalance {
+		EvmImbalance(Balances::burn(to_lunes(amount)))
+	}
+
+	fn issue(amount: Balance) -> Self::NegativeImbalance {
+		EvmImbalance(Balances::issue(to_lunes(amount)))
+	}
+
+	fn free_balance(who: &AccountId) -> Balance {
+		to_evm(Balances::free_balance(who))
+	}
+
+	fn ensure_can_withdraw(
+		who: &AccountId,
+		amount: Balance,
+		reasons: WithdrawReasons,
+		new_balance: Balance,
+	) -> DispatchResult {
+		Balances::ensure_can_withdraw(who, to_lunes_ceil(amount), reasons, to_lunes(new_balance))
+	}
+
+	fn transfer(
+		source: &AccountId,
+		dest: &AccountId,
+		value: Balance,
+		existence_requirement: ExistenceRequirement,
+	) -> DispatchResult {
+		<Balances as Currency<AccountId>>::transfer(
+			source,
+			dest,
+			to_lunes(value),
+			existence_requirement,
+		)
+	}
+
+	fn slash(who: &AccountId, value: Balance) -> (Self::NegativeImbalance, Balance) {
+		let (slashed, remaining) = Balances::slash(who, to_lunes_ceil(value));
+		(EvmImbalance(slashed), to_evm(remaining))
+	}
+
+	fn deposit_into_existing(
+		who: &AccountId,
+		value: Balance,
+	) -> Result<Self::PositiveImbalance, DispatchError> {
+		Balances::deposit_into_existing(who, to_lunes(value)).map(EvmImbalance)
+	}
+
+	fn deposit_creating(who: &AccountId, value: Balance) -> Self::PositiveImbalance {
+		EvmImbalance(Balances::deposit_creating(who, to_lunes(value)))
+	}
+
+	fn withdraw(
+		who: &AccountId,
+		value: Balance,
+		reasons: WithdrawReasons,
+		liveness: ExistenceRequirement,
+	) -> Result<Self::NegativeImbalance, DispatchError> {
+		Balances::withdraw(who, to_lunes_ceil(value), reasons, liveness).map(EvmImbalance)
+	}
+
+	fn make_free_balance_be(
+		who: &AccountId,
+		balance: Balance,
+	) -> SignedImbalance<Balance, Self::PositiveImbalance> {
+		match Balances::make_free_balance_be(who, to_lunes(balance)) {
+			SignedImbalance::Positive(imbalance) =>
+				SignedImbalance::Positive(EvmImbalance(imbalance)),
+			SignedImbalance::Negative(imbalance) =>
+				SignedImbalance::Negative(EvmImbalance(imbalance)),
+		}
+	}
+}
+
+impl Inspect<AccountId> for EvmBalances {
+	type Balance = Balance;
+
+	fn total_issuance() -> Balance {
+		to_evm(<Balances as Inspect<AccountId>>::total_issuance())
+	}
+
+	fn minimum_balance() -> Balance {
+		to_evm(<Balances as Inspect<AccountId>>::minimum_balance())
+	}
+
+	fn balance(who: &AccountId) -> Balance {
+		to_evm(<Balances as Inspect<AccountId>>::balance(who))
+	}
+
+	fn reducible_balance(who: &AccountId, keep_alive: bool) -> Balance {
+		to_evm(<Balances as Inspect<AccountId>>::reducible_balance(who, keep_alive))
+	}
+
+	fn can_deposit(who: &AccountId, amount: Balance, mint: bool) -> DepositConsequence {
+		<Balances as Inspect<AccountId>>::can_deposit(who, to_lunes(amount), mint)
+	}
+
+	fn can_withdraw(who: &AccountId, amount: Balance) -> WithdrawConsequence<Balance> {
+		match <Balances as Inspect<AccountId>>::can_withdraw(who, to_lunes_ceil(amount)) {
+			WithdrawConsequence::ReducedToZero(dust) =>
+				WithdrawConsequence::ReducedToZero(to_evm(dust)),
+			consequence => consequence,
+		}
+	}
+}
+
+/// Hands the fees `pallet_evm` charged through [`EvmBalances`] to `OU` in LUNES.
+pub struct FeesInLunes<OU>(PhantomData<OU>);
+
+impl<OU: OnUnbalanced<NegativeImbalance>> OnUnbalanced<EvmImbalance<NegativeImbalance>>
+	for FeesInLunes<OU>
+{
+	fn on_nonzero_unbalanced(fees: EvmImbalance<NegativeImbalance>) {
+		OU::on_unbalanced(fees.0)
+	}
+}
+
+/// Reports the block author to the EVM as the address of its Aura key.
+pub struct FindAuthorTruncated<F>(PhantomData<F>);
+
+impl<F: FindAuthor<u32>> FindAuthor<H160> for FindAuthorTruncated<F> {
+	fn find_author<'a, I>(digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		let author_index = F::find_author(digests)?;
+		let authority_id = Aura::authorities().get(author_index as usize)?.clone();
+		Some(H160::from_slice(&authority_id.to_raw_vec()[4..24]))
+	}
+}
+
+/// The precompiles of the Ethereum mainnet at their usual addresses `0x01` to `0x05`.
+pub struct LunesPrecompiles<R>(PhantomData<R>);
+
+impl<R> Default for LunesPrecompiles<R> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<R> LunesPrecompiles<R> {
+	pub fn used_addresses() -> [H160; 5] {
+		[hash(1), hash(2), hash(3), hash(4), hash(5)]
+	}
+}
+
+impl<R: pallet_evm::Config> PrecompileSet for LunesPrecompiles<R> {
+	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+		match handle.code_address() {
+			a if a == hash(1) => Some(ECRecover::execute(handle)),
+			a if a == hash(2) => Some(Sha256::execute(handle)),
+			a if a == hash(3) => Some(Ripemd160::execute(handle)),
+			a if a == hash(4) => Some(Identity::execute(handle)),
+			a if a == hash(5) => Some(Modexp::execute(handle)),
+			_ => None,
+		}
+	}
+
+	fn is_precompile(&self, address: H160) -> bool {
+		Self::used_addresses().contains(&address)
+	}
+}
+
+fn hash(a: u64) -> H160 {
+	H160::from_low_u64_be(a)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{constants::currency::UNIT, EvmAccounts, Runtime, RuntimeOrigin, Treasury, EVM};
+	use frame_support::{assert_ok, traits::GenesisBuild};
+	use pallet_evm::EnsureAddressOrigin;
+	use sp_io::hashing::blake2_256;
+	use sp_runtime::Permill;
+
+	type CallOrigin = <Runtime as pallet_evm::Config>::CallOrigin;
+
+	fn new_test_ext(balances: Vec<(AccountId, Balance)>) -> sp_io::TestExternalities {
+		let mut storage =
+			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> { balances }
+			.assimilate_storage(&mut storage)
+			.unwrap();
+		storage.into()
+	}
+
+	#[test]
+	fn transfer_gas_costs_target_fee() {
+		let (price, _) = LunesGasPrice::min_gas_price();
+		let fee = (price * U256::from(TRANSFER_GAS)).as_u128();
+		assert!(fee <= TargetTransferFee::get() * SCALE);
+		assert!(TargetTransferFee::get() * SCALE - fee < TRANSFER_GAS);
+		assert_eq!(to_lunes_ceil(fee), TargetTransferFee::get());
+	}
+
+	#[test]
+	fn addresses_map_to_accounts_both_ways() {
+		let account = AccountId::new([2; 32]);
+		let address = H160::repeat_byte(2);
+
+		new_test_ext(vec![(account.clone(), UNIT)]).execute_with(|| {
+			// Until it is claimed, the address acts as the account it hashes to.
+			let hashed = blake2_256(&[&b"evm:"[..], address.as_bytes()].concat());
+			assert_eq!(EvmAccounts::account_of(address), AccountId::new(hashed));
+			assert!(CallOrigin::ensure_address_origin(
+				&address,
+				RuntimeOrigin::signed(account.clone())
+			)
+			.is_err());
+
+			assert_ok!(EvmAccounts::claim_default_address(RuntimeOrigin::signed(account.clone())));
+			assert_eq!(EvmAccounts::account_of(address), account);
+			assert_eq!(EvmAccounts::address_of(&account), Some(address));
+			assert_eq!(
+				CallOrigin::ensure_address_origin(&address, RuntimeOrigin::signed(account.clone())),
+				Ok(account.clone()),
+			);
+			assert!(CallOrigin::ensure_address_origin(
+				&H160::repeat_byte(3),
+				RuntimeOrigin::signed(account),
+			)
+			.is_err());
+		});
+	}
+
+	#[test]
+	fn evm_balances_have_18_decimals() {
+		let alice = AccountId::new([1; 32]);
+		let bob = AccountId::new([2; 32]);
+
+		new_test_ext(vec![(alice.clone(), UNIT), (bob.clone(), UNIT)]).execute_with(|| {
+			assert_eq!(EvmBalances::free_balance(&alice), 10u128.pow(18));
+			assert_eq!(
+				<EvmBalances as Currency<AccountId>>::minimum_balance(),
+				crate::EXISTENTIAL_DEPOSIT * SCALE
+			);
+
+			// Fractions of the smallest LUNES unit are not transferred.
+			assert_ok!(EvmBalances::transfer(
+				&alice,
+				&bob,
+				3 * SCALE + SCALE / 2,
+				ExistenceRequirement::AllowDeath
+			));
+			assert_eq!(Balances::free_balance(&alice), UNIT - 3);
+			assert_eq!(Balances::free_balance(&bob), UNIT + 3);
+
+			// ...but are charged in full when withdrawn.
+			let withdrawn = EvmBalances::withdraw(
+				&alice,
+				SCALE / 2,
+				WithdrawReasons::FEE,
+				ExistenceRequirement::AllowDeath,
+			)
+			.unwrap();
+			assert_eq!(withdrawn.peek(), SCALE);
+			assert_eq!(Balances::free_balance(&alice), UNIT - 4);
+		});
+	}
+
+	#[test]
+	fn call_fees_are_split() {
+		let alice = AccountId::new([1; 32]);
+		let source = TruncatedAddress::convert(alice.clone());
+		let endowment = 1_000 * UNIT;
+
+		new_test_ext(vec![(alice.clone(), endowment)]).execute_with(|| {
+			assert_ok!(EvmAccounts::claim_default_address(RuntimeOrigin::signed(alice.clone())));
+			let (price, _) = LunesGasPrice::min_gas_price();
+			let pot = Balances::free_balance(&Treasury::account_id());
+
+			assert_ok!(EVM::call(
+				RuntimeOrigin::signed(alice.clone()),
+				source,
+				H160::repeat_byte(2),
+				Vec::new(),
+				U256::zero(),
+				TRANSFER_GAS as u64,
+				price,
+				None,
+				None,
+				Vec::new(),
+			));
+
+			// The fee is charged in LUNES, rounded up to the smallest unit.
+			let fee = TargetTransferFee::get();
+			assert_eq!(Balances::free_balance(&alice), endowment - fee);
+			// Without a block author and below the burn threshold, the treasury keeps the rest.
+			let author = Permill::from_parts(750_000).mul_floor(fee);
+			assert_eq!(Balances::free_balance(&Treasury::account_id()), pot + fee - author);
+		});
+	}
+}
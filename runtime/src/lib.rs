@@ -13,11 +13,26 @@ pub mod constants;
 mod voter_bags;
 /// Runtime API definition for assets.
 pub mod assets_api;
-use hex_literal::hex;
-use codec::{Decode, Encode};
+/// Runtime API definition for nfts enumeration.
+pub mod nfts_api;
+/// Migrations run on the next runtime upgrade.
+pub mod migrations;
+/// Runtime API definition for the LUNES supply.
+pub mod supply_api;
+/// Gas price, precompiles and address mapping of the EVM.
+pub mod evm;
+/// Runtime API definition for multisig operations.
+pub mod multisig_api;
+/// Runtime API definition for vesting schedules.
+pub mod vesting_api;
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{traits::OnUnbalanced, weights::ConstantMultiplier};
+use pallet_ethereum::{Call::transact, PostLogContent, Transaction as EthereumTransaction};
+use pallet_evm::{Account as EVMAccount, FeeCalculator, Runner};
 
 use pallet_grandpa::AuthorityId as GrandpaId;
+use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
+use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 
 use pallet_election_provider_multi_phase::SolutionAccuracyOf;
@@ -27,16 +42,19 @@ use frame_election_provider_support::{
 };
 
 use sp_api::impl_runtime_apis;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, H160, H256, U256};
 use sp_runtime::{
 	create_runtime_str, generic::{self, Era}, impl_opaque_keys,
 	traits::{
 		self, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
-		OpaqueKeys, StaticLookup, SaturatedConversion,
+		OpaqueKeys, StaticLookup, SaturatedConversion, Bounded, Convert, DispatchInfoOf,
+		Dispatchable, PostDispatchInfoOf, UniqueSaturatedInto, ConvertInto,
 	},
 	curve::PiecewiseLinear,
-	transaction_validity::{TransactionSource, TransactionValidity, TransactionPriority},
-	ApplyExtrinsicResult, MultiSignature,Percent
+	transaction_validity::{
+		TransactionSource, TransactionValidity, TransactionValidityError, TransactionPriority,
+	},
+	ApplyExtrinsicResult, FixedPointNumber, FixedU128, MultiSignature,Percent, RuntimeDebug,
 };
 use frame_system::{
 	EnsureRoot, EnsureSigned, EnsureWithSuccess,
@@ -49,7 +67,7 @@ use sp_version::RuntimeVersion;
 
 use node_primitives::{AccountIndex, Moment};
 use constants::{currency::*, time::*};
-use chain_extension::Psp22Extension;
+use chain_extension::LunesChainExtension;
 #[cfg(any(feature = "std", test))]
 pub use pallet_staking::StakerStatus;
 
@@ -62,7 +80,8 @@ pub use frame_support::{
 	traits::{
 		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
 		U128CurrencyToVote, Contains,Everything,Nothing,ConstBool,EqualPrivilegeOnly,EitherOfDiverse,
-		AsEnsureOriginWithArg,Currency as FrameCurrency,Imbalance,
+		AsEnsureOriginWithArg,Currency as FrameCurrency,Imbalance,InstanceFilter,WithdrawReasons,
+		EitherOf,
 		tokens::{nonfungibles_v2::Inspect},ChangeMembers,InitializeMembers,
 	},
 	weights::{
@@ -77,10 +96,10 @@ pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 
-use pallet_transaction_payment::{ConstFeeMultiplier,CurrencyAdapter, Multiplier};
+use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Permill, Perquintill};
 use pallet_nfts::PalletFeatures;
 
 /// An index to a block.
@@ -106,6 +125,8 @@ impl_opaque_keys! {
 	pub struct SessionKeys {
 		pub aura: Aura,
 		pub grandpa: Grandpa,
+		pub im_online: ImOnline,
+		pub authority_discovery: AuthorityDiscovery,
 	}
 }
 
@@ -123,7 +144,7 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 106,
+	spec_version: 128,
 	impl_version: 2,
 	apis: RUNTIME_API_VERSIONS,
 	transaction_version: 1,
@@ -224,15 +245,22 @@ impl pallet_aura::Config for Runtime {
 	type MaxAuthorities = ConstU32<32>;
 }
 
+parameter_types! {
+	// Keep the session of every set id for as long as an offence can still be slashed.
+	pub const MaxSetIdSessionEntries: u64 =
+		BondingDuration::get() as u64 * SessionsPerEra::get() as u64;
+}
+
 impl pallet_grandpa::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 
 	type WeightInfo = ();
 	type MaxAuthorities = ConstU32<32>;
-	type MaxSetIdSessionEntries = ConstU64<0>;
+	type MaxSetIdSessionEntries = MaxSetIdSessionEntries;
 
-	type KeyOwnerProof = sp_core::Void;
-	type EquivocationReportSystem = ();
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
+	type EquivocationReportSystem =
+		pallet_grandpa::EquivocationReportSystem<Self, Offences, Historical, ReportLongevity>;
 }
 
 impl pallet_timestamp::Config for Runtime {
@@ -259,22 +287,44 @@ impl pallet_balances::Config for Runtime {
 	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;	
 }
+parameter_types! {
+	/// Fee charged for the weight of a balance transfer, the reference extrinsic of
+	/// `WeightToFeeLunes`. The length fee comes on top.
+	pub const TargetTransferFee: Balance = MILLIUNIT;
+	pub const TransactionByteFee: Balance = 1 * NANOUNIT;
+	/// Fees go up once blocks are more than a quarter full.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(3, 100_000);
+	/// Fees never drop below the ones given by `WeightToFeeLunes`.
+	pub MinimumMultiplier: Multiplier = Multiplier::one();
+	pub MaximumMultiplier: Multiplier = Bounded::max_value();
+}
+
+/// Weight of the reference extrinsic: a balance transfer, including the base extrinsic weight.
+fn reference_transfer_weight() -> Weight {
+	use pallet_balances::WeightInfo;
+
+	RuntimeBlockWeights::get()
+		.get(DispatchClass::Normal)
+		.base_extrinsic
+		.saturating_add(<Runtime as pallet_balances::Config>::WeightInfo::transfer())
+}
+
+/// Linear weight to fee curve charging `TargetTransferFee` for the weight of a transfer.
 pub struct WeightToFeeLunes;
 impl WeightToFeePolynomial for WeightToFeeLunes {
 	type Balance = Balance;
-	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {		
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let p = TargetTransferFee::get();
+		let q = Balance::from(reference_transfer_weight().ref_time());
 		smallvec![WeightToFeeCoefficient {
 			degree: 1,
 			negative: false,
-			coeff_frac:  Perbill::zero(),
-			coeff_integer: 1 / 100,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
 		}]
 	}
 }
-parameter_types! {
-	pub FeeMultiplier: Multiplier = Multiplier::one();
-	pub const TransactionByteFee: Balance = 1 * NANOUNIT;
-}
 type NegativeImbalance = <Balances as FrameCurrency<AccountId>>::NegativeImbalance;
 
 pub struct Author;
@@ -285,46 +335,63 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 		}
 	}
 }
-pub struct TreasuryLunes;
-impl OnUnbalanced<NegativeImbalance> for TreasuryLunes {
-	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
-		
-		let recipient: AccountId = hex![
-				"2c11d2aff81147e5522539c51c1cb87bae94a0865d214f3983f3557a6732f26a"
-			].into();		
-		Balances::resolve_creating(&recipient, amount);
-	}
-}
-fn get_burn_lunes<T: pallet_balances::Config>(amount: Balance) -> () {
-	let _ = Balances::burn(amount);
-}
-fn get_total_issuance<T: pallet_balances::Config>() -> Balance {
-    Balances::total_issuance()
-}
-
 pub struct DealWithFees;
 impl OnUnbalanced<NegativeImbalance> for DealWithFees {
 	fn on_unbalanceds<B>(mut fees_then_tips: impl Iterator<Item = NegativeImbalance>) {
-		if let Some(mut fees) = fees_then_tips.next() {			
-			
+		if let Some(mut fees) = fees_then_tips.next() {
 			if let Some(tips) = fees_then_tips.next() {
 				tips.merge_into(&mut fees);
 			}
-			// for fees, 12.5% to treasury, 75% to Node e and 12.5% to Burn 
-			let split_fee = fees.ration(25, 75);			
-			
-			Author::on_unbalanced(split_fee.1);
-			
-			let total_issuance: Balance = get_total_issuance::<Runtime>();
-			if total_issuance > (50_000_000 * UNIT) {
-				let split_burn = split_fee.0.ration(50, 50);
-				TreasuryLunes::on_unbalanced(split_burn.0);
-				get_burn_lunes::<Runtime>(split_burn.1.peek());
-			}else{
-				TreasuryLunes::on_unbalanced(split_fee.0);
-			}
+			Self::on_unbalanced(fees);
 		}
 	}
+
+	// `pallet_evm` hands over the fees of EVM transactions through this one.
+	fn on_nonzero_unbalanced(fees: NegativeImbalance) {
+		// The shares and the burn threshold are governed on chain by `pallet_fee_split`.
+		let (author, treasury, burn) = FeeSplit::split_fees(fees);
+		Author::on_unbalanced(author);
+		Treasury::on_unbalanced(treasury);
+		Burn::on_unbalanced(burn);
+	}
+}
+
+parameter_types! {
+	// 75% to the block author, 12.5% to the treasury and 12.5% burned.
+	pub const DefaultFeeSplit: pallet_fee_split::FeeSplit = pallet_fee_split::FeeSplit {
+		author: Permill::from_parts(750_000),
+		treasury: Permill::from_parts(125_000),
+		burn: Permill::from_parts(125_000),
+	};
+	pub const DefaultBurnThreshold: Balance = 50_000_000 * UNIT;
+}
+
+/// Reports the active staking era to `pallet_burn`.
+pub struct ActiveEra;
+impl Get<u32> for ActiveEra {
+	fn get() -> u32 {
+		Staking::active_era().map(|era| era.index).unwrap_or_default()
+	}
+}
+
+parameter_types! {
+	pub const BurnHistoryDepth: u32 = 84;
+}
+
+impl pallet_burn::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type CurrentEra = ActiveEra;
+	type HistoryDepth = BurnHistoryDepth;
+}
+
+impl pallet_fee_split::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type UpdateOrigin = EnsureRootOrHalfCouncil;
+	type DefaultSplit = DefaultFeeSplit;
+	type DefaultBurnThreshold = DefaultBurnThreshold;
+	type WeightInfo = pallet_fee_split::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_transaction_payment::Config for Runtime {
@@ -333,7 +400,13 @@ impl pallet_transaction_payment::Config for Runtime {
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = WeightToFeeLunes;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
-	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
+	type FeeMultiplierUpdate = TargetedFeeAdjustment<
+		Self,
+		TargetBlockFullness,
+		AdjustmentVariable,
+		MinimumMultiplier,
+		MaximumMultiplier,
+	>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -495,7 +568,7 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type Fallback = onchain::OnChainExecution<OnChainSeqPhragmen>;
 	type GovernanceFallback = onchain::OnChainExecution<OnChainSeqPhragmen>;
 	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Self>, OffchainRandomBalancing>;
-	type ForceOrigin = EnsureRoot<AccountId>; // TODO EnsureRootOrHalfCouncil
+	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type MaxElectableTargets = MaxElectableTargets;
 	type MaxWinners = MaxActiveValidators;
 	type MaxElectingVoters = MaxElectingVoters;
@@ -536,7 +609,7 @@ parameter_types! {
 	pub const OffendingValidatorsThreshold: Perbill = Perbill::from_percent(17);
 	pub OffchainRepeat: BlockNumber = 5;
 	pub HistoryDepth: u32 = 84;
-	//pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
+	pub const RewardCurve: &'static PiecewiseLinear<'static> = &REWARD_CURVE;
 	pub const MinValidatorStake: Balance = 100;
 }
 
@@ -558,21 +631,22 @@ impl pallet_staking::Config for Runtime {
 	type UnixTime = Timestamp;
 	type CurrencyToVote = U128CurrencyToVote;
 	
-	type RewardRemainder = TreasuryLunes; // TODO Treasury
+	type RewardRemainder = Treasury;
 	type RuntimeEvent = RuntimeEvent;
-	type Slash = TreasuryLunes; // TODO Treasury send the slashed funds to the treasury.
+	type Slash = Treasury; // send the slashed funds to the treasury.
 	type Reward = (); // rewards are minted from the void
 	type SessionsPerEra = SessionsPerEra;
 	type BondingDuration = BondingDuration;
 	type SlashDeferDuration = SlashDeferDuration;
-	/// TODO A super-majority of the council can cancel the slash.
-	// type AdminOrigin = EitherOfDiverse<
-	// 	EnsureRoot<AccountId>,
-	// 	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
-	// >;
-	type AdminOrigin = EnsureRoot<AccountId>;
+	/// A super-majority of the council can cancel the slash.
+	type AdminOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
+	>;
 	type SessionInterface = Self;
-	type EraPayout = ();
+	// Stakers are paid along `REWARD_CURVE`; up to its maximum inflation, the rest goes to the
+	// treasury through `RewardRemainder`.
+	type EraPayout = pallet_staking::ConvertCurve<RewardCurve>;
 	type NextNewSession = Session;
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
@@ -583,11 +657,49 @@ impl pallet_staking::Config for Runtime {
 	type TargetList = pallet_staking::UseValidatorsMap<Self>;
 	type MaxUnlockingChunks = ConstU32<32>;
 	type HistoryDepth = HistoryDepth;
-	type OnStakerSlash = (); // NominationPools
+	type OnStakerSlash = NominationPools;
 	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
 	type BenchmarkingConfig = StakingBenchmarkingConfig;
 }
 
+parameter_types! {
+	pub const PostUnbondPoolsWindow: u32 = 4;
+	pub const NominationPoolsPalletId: PalletId = PalletId(*b"py/nopls");
+	pub const MaxPointsToBalance: u8 = 10;
+}
+
+pub struct BalanceToU256;
+impl Convert<Balance, sp_core::U256> for BalanceToU256 {
+	fn convert(balance: Balance) -> sp_core::U256 {
+		sp_core::U256::from(balance)
+	}
+}
+
+pub struct U256ToBalance;
+impl Convert<sp_core::U256, Balance> for U256ToBalance {
+	fn convert(n: sp_core::U256) -> Balance {
+		n.try_into().unwrap_or(Balance::max_value())
+	}
+}
+
+/// Lets holders below `MIN_NOMINATOR_BOND` stake together: every pool bonds and nominates through
+/// `Staking` with its own stash, so its nominations end up in `VoterList` like any other.
+impl pallet_nomination_pools::Config for Runtime {
+	type WeightInfo = pallet_nomination_pools::weights::SubstrateWeight<Runtime>;
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type RewardCounter = FixedU128;
+	type BalanceToU256 = BalanceToU256;
+	type U256ToBalance = U256ToBalance;
+	type Staking = Staking;
+	type PostUnbondingPoolsWindow = PostUnbondPoolsWindow;
+	type MaxMetadataLen = ConstU32<256>;
+	// Must not exceed the `MaxUnlockingChunks` of `Staking`.
+	type MaxUnbonding = ConstU32<8>;
+	type PalletId = NominationPoolsPalletId;
+	type MaxPointsToBalance = MaxPointsToBalance;
+}
+
 parameter_types! {
 	pub const SessionPeriod: u32 = 30;
 	pub const Offset: u32 = 0;
@@ -618,6 +730,204 @@ impl pallet_utility::Config for Runtime {
 	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	// One storage item; key size is 32 and value size is 16 + 16 + 32 + 32 * MaxSignatories.
+	pub const DepositBase: Balance = 1 * UNIT;
+	// Additional storage item size of 32 bytes per signatory.
+	pub const DepositFactor: Balance = 10 * MILLIUNIT;
+	pub const MaxSignatories: u32 = 100;
+}
+
+impl pallet_multisig::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type DepositBase = DepositBase;
+	type DepositFactor = DepositFactor;
+	type MaxSignatories = MaxSignatories;
+	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
+}
+
+/// The pending operations of the multisig account `multisig`, oldest first.
+fn pending_multisig_operations(
+	multisig: &AccountId,
+) -> Vec<multisig_api::PendingOperation<AccountId, Balance, BlockNumber>> {
+	let mut operations = pallet_multisig::Multisigs::<Runtime>::iter_prefix(multisig)
+		.map(|(call_hash, operation)| multisig_api::PendingOperation {
+			call_hash: H256::from(call_hash),
+			height: operation.when.height,
+			index: operation.when.index,
+			depositor: operation.depositor,
+			deposit: operation.deposit,
+			approvals: operation.approvals.into_inner(),
+		})
+		.collect::<Vec<_>>();
+	operations.sort_by_key(|operation| (operation.height, operation.index));
+	operations
+}
+
+parameter_types! {
+	// One storage item; key size 32, value size 8.
+	pub const ProxyDepositBase: Balance = 1 * UNIT;
+	// Additional storage item size of 33 bytes.
+	pub const ProxyDepositFactor: Balance = 10 * MILLIUNIT;
+	pub const MaxProxies: u16 = 32;
+	// One storage item; key size 32, value size 16.
+	pub const AnnouncementDepositBase: Balance = 1 * UNIT;
+	// Additional storage item size of 68 bytes.
+	pub const AnnouncementDepositFactor: Balance = 20 * MILLIUNIT;
+	pub const MaxPending: u16 = 32;
+}
+
+/// The calls a proxy may dispatch on behalf of the account that added it.
+///
+/// `Utility` is allowed next to the pallets of each type so that calls can be batched: every call
+/// of the batch still goes through the same filter.
+#[derive(
+	Copy,
+	Clone,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	MaxEncodedLen,
+	scale_info::TypeInfo,
+)]
+pub enum ProxyType {
+	/// Any call.
+	Any,
+	/// Any call that cannot move funds out of the account.
+	NonTransfer,
+	/// Democracy, council and treasury calls.
+	Governance,
+	/// Staking, session key and bags list calls, so that stash keys can stay cold.
+	Staking,
+	/// Calls of `pallet_assets`.
+	Assets,
+	/// Calls of `pallet_nfts`.
+	Nfts,
+	/// Calls of `pallet_contracts`.
+	Contracts,
+	/// Judgements of an identity registrar.
+	IdentityJudgement,
+}
+
+impl Default for ProxyType {
+	fn default() -> Self {
+		Self::Any
+	}
+}
+
+impl InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, c: &RuntimeCall) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(
+				c,
+				RuntimeCall::Balances(..) |
+					RuntimeCall::Assets(..) |
+					RuntimeCall::Nfts(..) |
+					RuntimeCall::Contracts(..) |
+					RuntimeCall::Swap(..) |
+					RuntimeCall::EVM(..) |
+					RuntimeCall::Ethereum(..) |
+					// A claimed address spends the funds of the account in the EVM.
+					RuntimeCall::EvmAccounts(..) |
+					RuntimeCall::Indices(pallet_indices::Call::transfer { .. }) |
+					RuntimeCall::Vesting(pallet_vesting::Call::vested_transfer { .. }) |
+					// Recovery configured by the proxy would let the friends it picks take over the
+					// account, and initiating a recovery reserves a deposit.
+					RuntimeCall::Recovery(pallet_recovery::Call::create_recovery { .. }) |
+					RuntimeCall::Recovery(pallet_recovery::Call::initiate_recovery { .. })
+			),
+			ProxyType::Governance => matches!(
+				c,
+				RuntimeCall::Democracy(..) |
+					RuntimeCall::Council(..) |
+					RuntimeCall::Treasury(..) |
+					RuntimeCall::Utility(..)
+			),
+			ProxyType::Staking => matches!(
+				c,
+				RuntimeCall::Staking(..) |
+					RuntimeCall::Session(..) |
+					RuntimeCall::VoterList(..) |
+					RuntimeCall::Utility(..)
+			),
+			ProxyType::Assets => matches!(c, RuntimeCall::Assets(..) | RuntimeCall::Utility(..)),
+			ProxyType::Nfts => matches!(c, RuntimeCall::Nfts(..) | RuntimeCall::Utility(..)),
+			ProxyType::Contracts =>
+				matches!(c, RuntimeCall::Contracts(..) | RuntimeCall::Utility(..)),
+			ProxyType::IdentityJudgement => matches!(
+				c,
+				RuntimeCall::Identity(pallet_identity::Call::provide_judgement { .. }) |
+					RuntimeCall::Utility(..)
+			),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		match (self, o) {
+			(x, y) if x == y => true,
+			(ProxyType::Any, _) => true,
+			(_, ProxyType::Any) => false,
+			(
+				ProxyType::NonTransfer,
+				ProxyType::Governance | ProxyType::Staking | ProxyType::IdentityJudgement,
+			) => true,
+			_ => false,
+		}
+	}
+}
+
+impl pallet_proxy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type ProxyType = ProxyType;
+	type ProxyDepositBase = ProxyDepositBase;
+	type ProxyDepositFactor = ProxyDepositFactor;
+	type MaxProxies = MaxProxies;
+	type WeightInfo = pallet_proxy::weights::SubstrateWeight<Runtime>;
+	type MaxPending = MaxPending;
+	type CallHasher = BlakeTwo256;
+	type AnnouncementDepositBase = AnnouncementDepositBase;
+	type AnnouncementDepositFactor = AnnouncementDepositFactor;
+}
+
+parameter_types! {
+	pub const MinVestedTransfer: Balance = 100 * UNIT;
+	pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+		WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+}
+
+impl pallet_vesting::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = MinVestedTransfer;
+	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
+	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+	const MAX_VESTING_SCHEDULES: u32 = 28;
+}
+
+/// The locked and vested amounts of the vesting schedules of `who` at the current block.
+fn vesting_balance(who: &AccountId) -> Option<vesting_api::VestingBalance<Balance>> {
+	let now = System::block_number();
+	let schedules = Vesting::vesting(who)?;
+	let (total, locked) =
+		schedules.iter().fold((0 as Balance, 0 as Balance), |(total, locked), schedule| {
+			(
+				total.saturating_add(schedule.locked()),
+				locked.saturating_add(schedule.locked_at::<ConvertInto>(now)),
+			)
+		});
+	Some(vesting_api::VestingBalance { total, locked, vested: total.saturating_sub(locked) })
+}
+
 impl pallet_offences::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type IdentificationTuple = pallet_session::historical::IdentificationTuple<Self>;
@@ -631,6 +941,26 @@ parameter_types! {
 	pub const MaxPeerDataEncodingSize: u32 = 1_000;
 }
 
+/// Validators send a heartbeat from their offchain worker every session. Those that neither
+/// heartbeat nor author a block are reported to `Offences`, which lets `Staking` slash them.
+impl pallet_im_online::Config for Runtime {
+	type AuthorityId = ImOnlineId;
+	type RuntimeEvent = RuntimeEvent;
+	type NextSessionRotation = Session;
+	type ValidatorSet = Historical;
+	// Validators still on the session keys of `migrations::UpgradeSessionKeys` cannot heartbeat.
+	type ReportUnresponsiveness = migrations::IgnorePendingRotation<Offences>;
+	type UnsignedPriority = ImOnlineUnsignedPriority;
+	type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
+	type MaxKeys = MaxKeys;
+	type MaxPeerInHeartbeats = MaxPeerInHeartbeats;
+	type MaxPeerDataEncodingSize = MaxPeerDataEncodingSize;
+}
+
+impl pallet_authority_discovery::Config for Runtime {
+	type MaxAuthorities = MaxAuthorities;
+}
+
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
 where
 	RuntimeCall: From<LocalCall>,
@@ -678,6 +1008,66 @@ impl frame_system::offchain::SigningTypes for Runtime {
 	type Signature = Signature;
 }
 
+/// Approximate EVM gas executed per second of weight.
+const GAS_PER_SECOND: u64 = 40_000_000;
+/// Weight charged per unit of gas.
+const WEIGHT_PER_GAS: u64 = WEIGHT_REF_TIME_PER_SECOND / GAS_PER_SECOND;
+
+parameter_types! {
+	/// As much gas as fits in the weight available to normal extrinsics.
+	pub BlockGasLimit: U256 = U256::from(
+		NORMAL_DISPATCH_RATIO * RuntimeBlockWeights::get().max_block.ref_time() / WEIGHT_PER_GAS,
+	);
+	pub WeightPerGas: Weight = Weight::from_parts(WEIGHT_PER_GAS, 0);
+	pub PrecompilesValue: evm::LunesPrecompiles<Runtime> = Default::default();
+}
+
+impl pallet_evm::Config for Runtime {
+	type FeeCalculator = evm::LunesGasPrice;
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
+	// A LUNES account calls the EVM with the address it claimed in `pallet_evm_accounts`.
+	type CallOrigin = pallet_evm_accounts::EnsureAddressClaimed<Self>;
+	type WithdrawOrigin = pallet_evm_accounts::EnsureAddressClaimed<Self>;
+	// Claimed addresses spend the balance of their account, the others of the account they hash to.
+	type AddressMapping = EvmAccounts;
+	// The EVM sees balances with 18 decimals.
+	type Currency = evm::EvmBalances;
+	type RuntimeEvent = RuntimeEvent;
+	type PrecompilesType = evm::LunesPrecompiles<Self>;
+	type PrecompilesValue = PrecompilesValue;
+	type ChainId = EVMChainId;
+	type BlockGasLimit = BlockGasLimit;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	// Gas fees are split like the fees of any other extrinsic.
+	type OnChargeTransaction =
+		pallet_evm::EVMCurrencyAdapter<evm::EvmBalances, evm::FeesInLunes<DealWithFees>>;
+	type OnCreate = ();
+	type FindAuthor = evm::FindAuthorTruncated<Aura>;
+}
+
+parameter_types! {
+	pub const PostBlockAndTxnHashes: PostLogContent = PostLogContent::BlockAndTxnHashes;
+}
+
+impl pallet_ethereum::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self>;
+	type PostLogContent = PostBlockAndTxnHashes;
+	type ExtraDataLength = ConstU32<30>;
+}
+
+impl pallet_evm_chain_id::Config for Runtime {}
+
+impl pallet_evm_accounts::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type FallbackMapping = pallet_evm::HashedAddressMapping<BlakeTwo256>;
+	type DefaultAddress = evm::TruncatedAddress;
+	type Currency = Balances;
+	type WeightInfo = pallet_evm_accounts::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const DepositPerItem: Balance = CONTRACT_DEPOSIT_PER_BYTE;
 	pub const DepositPerByte: Balance = CONTRACT_DEPOSIT_PER_BYTE;
@@ -714,7 +1104,7 @@ impl pallet_contracts::Config for Runtime {
 	type MaxStorageKeyLen = ConstU32<128>;
 	type UnsafeUnstableInterface = ConstBool<false>;
 	type MaxDebugBufferLen = ConstU32<{ 2 * 1024 * 1024 }>;
-	type ChainExtension = Psp22Extension;
+	type ChainExtension = LunesChainExtension;
 	
 }
 
@@ -729,7 +1119,7 @@ impl pallet_preimage::Config for Runtime {
 	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
-	type ManagerOrigin = EnsureRoot<AccountId>;
+	type ManagerOrigin = EnsureRootOrHalfTechnicalCommittee;
 	type BaseDeposit = PreimageBaseDeposit;
 	type ByteDeposit = PreimageByteDeposit;
 }
@@ -745,7 +1135,7 @@ impl pallet_scheduler::Config for Runtime {
 	type PalletsOrigin = OriginCaller;
 	type RuntimeCall = RuntimeCall;
 	type MaximumWeight = MaximumSchedulerWeight;
-	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type ScheduleOrigin = EnsureRootOrHalfCouncil;
 	#[cfg(feature = "runtime-benchmarks")]
 	type MaxScheduledPerBlock = ConstU32<512>;
 	#[cfg(not(feature = "runtime-benchmarks"))]
@@ -853,6 +1243,7 @@ impl pallet_democracy::Config for Runtime {
 		EnsureRoot<AccountId>,
 		pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 1>,
 	>;
+	// Blacklisting bans a proposal for good, so it takes a referendum.
 	type BlacklistOrigin = EnsureRoot<AccountId>;
 	// Any single technical committee member may veto a coming council proposal, however they can
 	// only do it once and it lasts only for the cool-off period.
@@ -881,6 +1272,8 @@ parameter_types! {
 	pub const MaximumReasonLength: u32 = 300;
 	pub const MaxApprovals: u32 = 100;
 	pub const MaxBalance: Balance = Balance::max_value();
+	/// Largest spend the council approves without a referendum.
+	pub const CouncilMaxSpend: Balance = 100_000 * UNIT;
 }
 impl pallet_treasury::Config for Runtime {
 	type PalletId = TreasuryPalletId;
@@ -904,7 +1297,14 @@ impl pallet_treasury::Config for Runtime {
 	type SpendFunds = Bounties;
 	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
 	type MaxApprovals = MaxApprovals;
-	type SpendOrigin = EnsureWithSuccess<EnsureRoot<AccountId>, AccountId, MaxBalance>;
+	type SpendOrigin = EitherOf<
+		EnsureWithSuccess<EnsureRoot<AccountId>, AccountId, MaxBalance>,
+		EnsureWithSuccess<
+			pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+			AccountId,
+			CouncilMaxSpend,
+		>,
+	>;
 }
 impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
 parameter_types! {
@@ -959,7 +1359,7 @@ impl pallet_assets::Config for Runtime {
 	type AssetIdParameter = codec::Compact<u32>;
 	type Currency = Balances;
 	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
-	type ForceOrigin = EnsureRoot<AccountId>;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type AssetDeposit = AssetDeposit;
 	type AssetAccountDeposit = ConstU128<UNIT>;
 	type MetadataDepositBase = MetadataDepositBase;
@@ -992,7 +1392,7 @@ impl pallet_nfts::Config for Runtime {
 	type CollectionId = u32;
 	type ItemId = u32;
 	type Currency = Balances;
-	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type CollectionDeposit = CollectionDeposit;
 	type ItemDeposit = ItemDeposit;
 	type MetadataDepositBase = MetadataDepositBase;
@@ -1016,6 +1416,50 @@ impl pallet_nfts::Config for Runtime {
 	type Locker = ();
 }
 
+/// Read-only views of the `pallet_nfts` ownership indexes, which the pallet keeps private.
+///
+/// `InspectEnumerable` can only iterate them from the start, while the enumeration API pages with
+/// a start key. Only the keys are read, so the item values are left undecoded.
+mod nfts_storage {
+	use super::AccountId;
+	use frame_support::{storage::types::Key, Blake2_128Concat};
+
+	#[frame_support::storage_alias]
+	pub type Account = StorageNMap<
+		Nfts,
+		(Key<Blake2_128Concat, AccountId>, Key<Blake2_128Concat, u32>, Key<Blake2_128Concat, u32>),
+		(),
+	>;
+
+	#[frame_support::storage_alias]
+	pub type Item = StorageDoubleMap<Nfts, Blake2_128Concat, u32, Blake2_128Concat, u32, ()>;
+}
+
+/// Up to `limit` `(collection, item)` pairs owned by `account`, after `start_key` if given.
+fn owned_items(account: &AccountId, start_key: Option<(u32, u32)>, limit: u32) -> Vec<(u32, u32)> {
+	use nfts_storage::Account;
+	match start_key {
+		Some((collection, item)) => Account::iter_key_prefix_from(
+			(account,),
+			Account::hashed_key_for((account, collection, item)),
+		)
+		.take(limit as usize)
+		.collect(),
+		None => Account::iter_key_prefix((account,)).take(limit as usize).collect(),
+	}
+}
+
+/// Up to `limit` items of `collection`, after `start_key` if given.
+fn collection_items(collection: u32, start_key: Option<u32>, limit: u32) -> Vec<u32> {
+	use nfts_storage::Item;
+	match start_key {
+		Some(item) => Item::iter_key_prefix_from(collection, Item::hashed_key_for(collection, item))
+			.take(limit as usize)
+			.collect(),
+		None => Item::iter_key_prefix(collection).take(limit as usize).collect(),
+	}
+}
+
 parameter_types! {
 	pub const ConfigDepositBase: Balance = 5 * UNIT;
 	pub const FriendDepositFactor: Balance = 50 * NANOUNIT;
@@ -1037,6 +1481,10 @@ type EnsureRootOrHalfCouncil = EitherOfDiverse<
 	EnsureRoot<AccountId>,
 	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
 >;
+type EnsureRootOrHalfTechnicalCommittee = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, TechnicalCollective, 1, 2>,
+>;
 parameter_types! {
 	pub const BasicDeposit: Balance = 10 * UNIT;       // 258 bytes on-chain
 	pub const FieldDeposit: Balance = 250 * NANOUNIT;        // 66 bytes on-chain
@@ -1086,7 +1534,7 @@ impl pallet_nicks::Config for Runtime {
     type Currency = Balances;
     type ReservationFee = NickReservationFee;
     type Slashed = Treasury;
-    type ForceOrigin = EnsureRoot<AccountId>;
+    type ForceOrigin = EnsureRootOrHalfCouncil;
     type MinLength = MinLengthNicks;
     type MaxLength = MaxLengthNicks;
 }
@@ -1130,17 +1578,25 @@ construct_runtime!(
 		Grandpa: pallet_grandpa,
 		Balances: pallet_balances,
 		TransactionPayment: pallet_transaction_payment,
+		FeeSplit: pallet_fee_split,
+		Burn: pallet_burn,
 		Sudo: pallet_sudo,
 
 		Authorship: pallet_authorship,
 		Utility: pallet_utility,
+		Multisig: pallet_multisig,
+		Proxy: pallet_proxy,
+		Vesting: pallet_vesting,
 		Offences: pallet_offences,
 		// staking related pallets
 		ElectionProviderMultiPhase: pallet_election_provider_multi_phase,
 		Historical: pallet_session::historical::{Pallet},
 		Staking: pallet_staking,
 		Session: pallet_session,
+		ImOnline: pallet_im_online,
+		AuthorityDiscovery: pallet_authority_discovery,
 		VoterList: pallet_bags_list::<Instance1>,
+		NominationPools: pallet_nomination_pools,
 		Democracy: pallet_democracy,
 		Council: pallet_collective::<Instance1>,
 		TechnicalCommittee: pallet_collective::<Instance2>,
@@ -1160,6 +1616,10 @@ construct_runtime!(
 		Nicks: pallet_nicks,
 		ScoredPool: pallet_scored_pool,
 		Swap:pallet_atomic_swap,
+		EVM: pallet_evm,
+		Ethereum: pallet_ethereum,
+		EVMChainId: pallet_evm_chain_id,
+		EvmAccounts: pallet_evm_accounts,
 	}
 );
 
@@ -1181,9 +1641,13 @@ pub type SignedExtra = (
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
 
-/// Unchecked extrinsic type as expected by this runtime.
+/// Unchecked extrinsic type as expected by this runtime. Ethereum transactions are carried
+/// unsigned and verified by `pallet_ethereum` itself.
 pub type UncheckedExtrinsic =
-	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
+	fp_self_contained::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
+/// Extrinsic type that has already been checked.
+pub type CheckedExtrinsic =
+	fp_self_contained::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra, H160>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
@@ -1193,8 +1657,65 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	migrations::Migrations,
 >;
 
+impl fp_self_contained::SelfContainedCall for RuntimeCall {
+	type SignedInfo = H160;
+
+	fn is_self_contained(&self) -> bool {
+		match self {
+			RuntimeCall::Ethereum(call) => call.is_self_contained(),
+			_ => false,
+		}
+	}
+
+	fn check_self_contained(&self) -> Option<Result<Self::SignedInfo, TransactionValidityError>> {
+		match self {
+			RuntimeCall::Ethereum(call) => call.check_self_contained(),
+			_ => None,
+		}
+	}
+
+	fn validate_self_contained(
+		&self,
+		info: &Self::SignedInfo,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		len: usize,
+	) -> Option<TransactionValidity> {
+		match self {
+			RuntimeCall::Ethereum(call) => call.validate_self_contained(info, dispatch_info, len),
+			_ => None,
+		}
+	}
+
+	fn pre_dispatch_self_contained(
+		&self,
+		info: &Self::SignedInfo,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		len: usize,
+	) -> Option<Result<(), TransactionValidityError>> {
+		match self {
+			RuntimeCall::Ethereum(call) =>
+				call.pre_dispatch_self_contained(info, dispatch_info, len),
+			_ => None,
+		}
+	}
+
+	fn apply_self_contained(
+		self,
+		info: Self::SignedInfo,
+	) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
+		match self {
+			call @ RuntimeCall::Ethereum(pallet_ethereum::Call::transact { .. }) =>
+				Some(call.dispatch(RuntimeOrigin::from(
+					pallet_ethereum::RawOrigin::EthereumTransaction(info),
+				))),
+			_ => None,
+		}
+	}
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -1207,6 +1728,9 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_utility, Utility]
+		[pallet_multisig, Multisig]
+		[pallet_proxy, Proxy]
+		[pallet_vesting, Vesting]
 		[pallet_offences, OffencesBench::<Runtime>]		
 		[pallet_democracy, Democracy]
 		[pallet_collective, Council]
@@ -1224,6 +1748,9 @@ mod benches {
 		[pallet_nicks,Nicks]
 		[pallet_scored_pool, ScoredPool]
 		[pallet_atomic_swap, Swap]
+		[pallet_fee_split, FeeSplit]
+		[pallet_evm_accounts, EvmAccounts]
+		[chain_extension, ChainExtensionBench::<Runtime>]
 	);
 }
 
@@ -1295,6 +1822,171 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			<Runtime as pallet_evm::Config>::ChainId::get()
+		}
+
+		fn account_basic(address: H160) -> EVMAccount {
+			let (account, _) = EVM::account_basic(&address);
+			account
+		}
+
+		fn gas_price() -> U256 {
+			let (gas_price, _) = <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+			gas_price
+		}
+
+		fn account_code_at(address: H160) -> Vec<u8> {
+			pallet_evm::AccountCodes::<Runtime>::get(address)
+		}
+
+		fn author() -> H160 {
+			<pallet_evm::Pallet<Runtime>>::find_author()
+		}
+
+		fn storage_at(address: H160, index: U256) -> H256 {
+			let mut tmp = [0u8; 32];
+			index.to_big_endian(&mut tmp);
+			pallet_evm::AccountStorages::<Runtime>::get(address, H256::from_slice(&tmp[..]))
+		}
+
+		fn call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
+			let mut config = <Runtime as pallet_evm::Config>::config().clone();
+			config.estimate = estimate;
+
+			<Runtime as pallet_evm::Config>::Runner::call(
+				from,
+				to,
+				data,
+				value,
+				gas_limit.unique_saturated_into(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				false,
+				true,
+				&config,
+			)
+			.map_err(|err| err.error.into())
+		}
+
+		fn create(
+			from: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
+			let mut config = <Runtime as pallet_evm::Config>::config().clone();
+			config.estimate = estimate;
+
+			<Runtime as pallet_evm::Config>::Runner::create(
+				from,
+				data,
+				value,
+				gas_limit.unique_saturated_into(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				false,
+				true,
+				&config,
+			)
+			.map_err(|err| err.error.into())
+		}
+
+		fn current_transaction_statuses() -> Option<Vec<fp_rpc::TransactionStatus>> {
+			pallet_ethereum::CurrentTransactionStatuses::<Runtime>::get()
+		}
+
+		fn current_block() -> Option<pallet_ethereum::Block> {
+			pallet_ethereum::CurrentBlock::<Runtime>::get()
+		}
+
+		fn current_receipts() -> Option<Vec<pallet_ethereum::Receipt>> {
+			pallet_ethereum::CurrentReceipts::<Runtime>::get()
+		}
+
+		fn current_all() -> (
+			Option<pallet_ethereum::Block>,
+			Option<Vec<pallet_ethereum::Receipt>>,
+			Option<Vec<fp_rpc::TransactionStatus>>,
+		) {
+			(
+				pallet_ethereum::CurrentBlock::<Runtime>::get(),
+				pallet_ethereum::CurrentReceipts::<Runtime>::get(),
+				pallet_ethereum::CurrentTransactionStatuses::<Runtime>::get(),
+			)
+		}
+
+		fn extrinsic_filter(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+		) -> Vec<EthereumTransaction> {
+			xts.into_iter()
+				.filter_map(|xt| match xt.0.function {
+					RuntimeCall::Ethereum(transact { transaction }) => Some(transaction),
+					_ => None,
+				})
+				.collect()
+		}
+
+		fn elasticity() -> Option<Permill> {
+			// The gas price is fixed, there is no EIP-1559 base fee.
+			None
+		}
+
+		fn gas_limit_multiplier_support() {}
+
+		fn pending_block(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+		) -> (Option<pallet_ethereum::Block>, Option<Vec<fp_rpc::TransactionStatus>>) {
+			use frame_support::traits::OnFinalize;
+
+			for ext in xts.into_iter() {
+				let _ = Executive::apply_extrinsic(ext);
+			}
+
+			Ethereum::on_finalize(System::block_number() + 1);
+
+			(
+				pallet_ethereum::CurrentBlock::<Runtime>::get(),
+				pallet_ethereum::CurrentTransactionStatuses::<Runtime>::get(),
+			)
+		}
+	}
+
+	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
+		fn convert_transaction(transaction: EthereumTransaction) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+			)
+		}
+	}
+
+	impl sp_authority_discovery::AuthorityDiscoveryApi<Block> for Runtime {
+		fn authorities() -> Vec<AuthorityDiscoveryId> {
+			AuthorityDiscovery::authorities()
+		}
+	}
+
 	impl sp_session::SessionKeys<Block> for Runtime {
 		fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
 			SessionKeys::generate(seed)
@@ -1317,23 +2009,24 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: sp_consensus_grandpa::EquivocationProof<
+			equivocation_proof: sp_consensus_grandpa::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
+			key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(equivocation_proof, key_owner_proof)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: sp_consensus_grandpa::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			Historical::prove((sp_consensus_grandpa::KEY_TYPE, authority_id))
+				.map(|proof| proof.encode())
+				.map(sp_consensus_grandpa::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -1387,6 +2080,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl assets_api::AssetsApi<
 		Block,
 		AccountId,
@@ -1397,6 +2091,75 @@ impl_runtime_apis! {
 		fn account_balances(account: AccountId) -> Vec<(u32, Balance)> {
 			Assets::account_balances(account)
 		}
+
+		fn asset_info(asset: u32) -> Option<assets_api::AssetInfo<Balance>> {
+			use frame_support::traits::fungibles::InspectMetadata;
+
+			let supply = Assets::maybe_total_supply(asset)?;
+			Some(assets_api::AssetInfo {
+				name: <Assets as InspectMetadata<AccountId>>::name(&asset),
+				symbol: <Assets as InspectMetadata<AccountId>>::symbol(&asset),
+				decimals: <Assets as InspectMetadata<AccountId>>::decimals(&asset),
+				supply,
+			})
+		}
+	}
+
+	impl pallet_nomination_pools_runtime_api::NominationPoolsApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn pending_rewards(member: AccountId) -> Balance {
+			NominationPools::api_pending_rewards(member).unwrap_or_default()
+		}
+
+		fn points_to_balance(pool_id: pallet_nomination_pools::PoolId, points: Balance) -> Balance {
+			NominationPools::api_points_to_balance(pool_id, points)
+		}
+
+		fn balance_to_points(
+			pool_id: pallet_nomination_pools::PoolId,
+			new_funds: Balance,
+		) -> Balance {
+			NominationPools::api_balance_to_points(pool_id, new_funds)
+		}
+	}
+
+	impl supply_api::SupplyApi<Block, Balance> for Runtime {
+		fn supply() -> supply_api::SupplyInfo<Balance> {
+			let total_issuance = Balances::total_issuance();
+			let treasury = Balances::free_balance(&Treasury::account_id());
+			supply_api::SupplyInfo {
+				total_issuance,
+				treasury,
+				total_burned: Burn::total_burned(),
+				circulating: total_issuance.saturating_sub(treasury),
+			}
+		}
+
+		fn burn_history() -> Vec<(u32, Balance)> {
+			Burn::burn_history().into_inner()
+		}
+	}
+
+	impl multisig_api::MultisigApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn pending_operations(
+			multisig: AccountId,
+		) -> Vec<multisig_api::PendingOperation<AccountId, Balance, BlockNumber>> {
+			pending_multisig_operations(&multisig)
+		}
+
+		fn multisig_account(mut signatories: Vec<AccountId>, threshold: u16) -> AccountId {
+			signatories.sort();
+			Multisig::multi_account_id(&signatories, threshold)
+		}
+	}
+
+	impl vesting_api::VestingApi<Block, AccountId, Balance> for Runtime {
+		fn vesting_balance(who: AccountId) -> Option<vesting_api::VestingBalance<Balance>> {
+			vesting_balance(&who)
+		}
 	}
 
 	impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime {
@@ -1443,6 +2206,20 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl nfts_api::NftsEnumerationApi<Block, AccountId, u32, u32> for Runtime {
+		fn owned_items(
+			account: AccountId,
+			start_key: Option<(u32, u32)>,
+			limit: u32,
+		) -> Vec<(u32, u32)> {
+			owned_items(&account, start_key, limit)
+		}
+
+		fn collection_items(collection: u32, start_key: Option<u32>, limit: u32) -> Vec<u32> {
+			collection_items(collection, start_key, limit)
+		}
+	}
+
 	impl pallet_contracts::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash> for Runtime
 	{
 		fn call(
@@ -1526,6 +2303,7 @@ impl_runtime_apis! {
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use pallet_offences_benchmarking::Pallet as OffencesBench;
 			use baseline::Pallet as BaselineBench;
+			use chain_extension::benchmarking::Pallet as ChainExtensionBench;
 
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
@@ -1543,10 +2321,12 @@ impl_runtime_apis! {
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use pallet_offences_benchmarking::Pallet as OffencesBench;
 			use baseline::Pallet as BaselineBench;
+			use chain_extension::benchmarking::Pallet as ChainExtensionBench;
 
 			impl frame_system_benchmarking::Config for Runtime {}
 			impl pallet_offences_benchmarking::Config for Runtime {}
 			impl baseline::Config for Runtime {}
+			impl chain_extension::benchmarking::Config for Runtime {}
 
 			use frame_support::traits::WhitelistedStorageKeys;
 			let whitelist: Vec<TrackedStorageKey> = AllPalletsWithSystem::whitelisted_storage_keys();
@@ -1586,10 +2366,761 @@ impl_runtime_apis! {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use frame_support::traits::WhitelistedStorageKeys;
+	use frame_support::{
+		dispatch::GetDispatchInfo,
+		traits::{GenesisBuild, WhitelistedStorageKeys},
+		weights::WeightToFee,
+	};
 	use sp_core::hexdisplay::HexDisplay;
 	use std::collections::HashSet;
 
+	fn new_test_ext(issuance: Balance) -> sp_io::TestExternalities {
+		let mut storage =
+			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+		pallet_balances::GenesisConfig::<Runtime> {
+			balances: vec![(AccountId::new([1; 32]), issuance)],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		storage.into()
+	}
+
+	type FeeMultiplierUpdate =
+		<Runtime as pallet_transaction_payment::Config>::FeeMultiplierUpdate;
+
+	fn split_fees(fees: Balance) -> (Balance, Balance, Balance) {
+		let (author, treasury, burn) = FeeSplit::split_fees(Balances::issue(fees));
+		(author.peek(), treasury.peek(), burn.peek())
+	}
+
+	#[test]
+	fn transfer_weight_costs_target_fee() {
+		new_test_ext(0).execute_with(|| {
+			let call = RuntimeCall::Balances(BalancesCall::transfer {
+				dest: AccountId::new([2; 32]).into(),
+				value: UNIT,
+			});
+			let info = call.get_dispatch_info();
+			let fee = TransactionPayment::compute_fee(0, &info, 0);
+			// Base and dispatch weight are converted separately, each rounding down.
+			assert!(fee <= TargetTransferFee::get() && fee + 2 >= TargetTransferFee::get());
+		});
+	}
+
+	#[test]
+	fn length_is_charged_per_byte() {
+		new_test_ext(0).execute_with(|| {
+			let call = RuntimeCall::System(SystemCall::remark { remark: vec![] });
+			let info = call.get_dispatch_info();
+			let fee = TransactionPayment::compute_fee(0, &info, 0);
+			assert!(fee > 0);
+			assert_eq!(
+				TransactionPayment::compute_fee(100, &info, 0),
+				fee + 100 * TransactionByteFee::get()
+			);
+		});
+	}
+
+	#[test]
+	fn weight_fee_scales_linearly() {
+		let weight = reference_transfer_weight();
+		assert_eq!(
+			WeightToFeeLunes::weight_to_fee(&weight.saturating_mul(10)),
+			10 * WeightToFeeLunes::weight_to_fee(&weight)
+		);
+		assert_eq!(WeightToFeeLunes::weight_to_fee(&Weight::zero()), 0);
+	}
+
+	#[test]
+	fn multiplier_does_not_drop_below_one() {
+		new_test_ext(0).execute_with(|| {
+			// An empty block pushes the multiplier down, but it stays at the minimum.
+			let next = FeeMultiplierUpdate::convert(Multiplier::one());
+			assert_eq!(next, Multiplier::one());
+		});
+	}
+
+	#[test]
+	fn multiplier_grows_with_full_blocks() {
+		new_test_ext(0).execute_with(|| {
+			let max_normal = RuntimeBlockWeights::get()
+				.get(DispatchClass::Normal)
+				.max_total
+				.unwrap_or_else(|| RuntimeBlockWeights::get().max_block);
+			System::set_block_consumed_resources(max_normal, 0);
+			let next = FeeMultiplierUpdate::convert(Multiplier::one());
+			assert!(next > Multiplier::one());
+		});
+	}
+
+	const MILLISECONDS_PER_YEAR: u64 = 1000 * 3600 * 24 * 36525 / 100;
+
+	fn era_payout(staked: Balance, issuance: Balance, era_duration: u64) -> (Balance, Balance) {
+		use pallet_staking::EraPayout;
+
+		<Runtime as pallet_staking::Config>::EraPayout::era_payout(staked, issuance, era_duration)
+	}
+
+	fn assert_close(actual: Balance, expected: Balance, tolerance: Balance) {
+		assert!(
+			actual.max(expected) - actual.min(expected) <= tolerance,
+			"{} is not within {} of {}",
+			actual,
+			tolerance,
+			expected,
+		);
+	}
+
+	#[test]
+	fn era_payout_follows_reward_curve() {
+		let issuance = TOTAL_INITIAL_ISSUANCE_LUNES;
+		let tolerance = issuance / 1_000;
+
+		// At the ideal stake, stakers receive the maximum inflation of 10% a year.
+		let (to_stakers, remainder) = era_payout(issuance / 2, issuance, MILLISECONDS_PER_YEAR);
+		assert_close(to_stakers, issuance / 10, tolerance);
+		assert_close(remainder, 0, tolerance);
+
+		// Without stake, stakers receive the minimum of 2.5% and the treasury the rest.
+		let (to_stakers, remainder) = era_payout(0, issuance, MILLISECONDS_PER_YEAR);
+		assert_close(to_stakers, issuance / 40, tolerance);
+		assert_close(to_stakers + remainder, issuance / 10, tolerance);
+	}
+
+	#[test]
+	fn several_eras_of_payouts() {
+		let era_duration =
+			(SessionsPerEra::get() * SessionPeriod::get()) as u64 * MILLISECS_PER_BLOCK;
+		let eras = 100;
+		let mut issuance = TOTAL_INITIAL_ISSUANCE_LUNES;
+		let mut paid_to_stakers = 0;
+
+		for _ in 0..eras {
+			let (to_stakers, remainder) = era_payout(issuance / 4, issuance, era_duration);
+			assert!(to_stakers > 0 && remainder > 0);
+			// Stakers and treasury together always receive the maximum inflation.
+			let max_payout = Perbill::from_rational(era_duration, MILLISECONDS_PER_YEAR) *
+				(issuance / 10);
+			assert_close(to_stakers + remainder, max_payout, max_payout / 1_000);
+
+			paid_to_stakers += to_stakers;
+			issuance += to_stakers + remainder;
+		}
+
+		let duration = Perbill::from_rational(eras * era_duration, MILLISECONDS_PER_YEAR);
+		let max_inflation = duration * (TOTAL_INITIAL_ISSUANCE_LUNES / 10);
+		assert!(paid_to_stakers < max_inflation);
+		assert!(issuance > TOTAL_INITIAL_ISSUANCE_LUNES + max_inflation * 99 / 100);
+	}
+
+	#[test]
+	fn pool_points_conversion_saturates() {
+		let balance = Balance::max_value();
+		assert_eq!(U256ToBalance::convert(BalanceToU256::convert(balance)), balance);
+		assert_eq!(U256ToBalance::convert(sp_core::U256::MAX), Balance::max_value());
+	}
+
+	#[test]
+	fn pool_unbonding_fits_staking_unlocking_chunks() {
+		let max_unbonding: u32 = <Runtime as pallet_nomination_pools::Config>::MaxUnbonding::get();
+		let max_chunks: u32 = <Runtime as pallet_staking::Config>::MaxUnlockingChunks::get();
+		assert!(max_unbonding <= max_chunks);
+	}
+
+	#[test]
+	fn grandpa_equivocation_is_slashed() {
+		use frame_support::assert_ok;
+		use sp_consensus_grandpa::{
+			localized_payload, AuthoritySignature, Equivocation, EquivocationProof, KEY_TYPE,
+		};
+		use sp_core::{ed25519, sr25519, Pair};
+
+		let stash = AccountId::new([1; 32]);
+		let bond = MIN_VALIDATOR_BOND;
+		let grandpa = ed25519::Pair::from_string("//Alice", None).unwrap();
+		let session_key = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		let keys = SessionKeys {
+			aura: session_key.into(),
+			grandpa: grandpa.public().into(),
+			im_online: session_key.into(),
+			authority_discovery: session_key.into(),
+		};
+		let storage = GenesisConfig {
+			balances: BalancesConfig { balances: vec![(stash.clone(), 2 * bond)] },
+			session: SessionConfig { keys: vec![(stash.clone(), stash.clone(), keys)] },
+			staking: StakingConfig {
+				validator_count: 1,
+				minimum_validator_count: 1,
+				stakers: vec![(stash.clone(), stash.clone(), bond, StakerStatus::Validator)],
+				..Default::default()
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+
+		sp_io::TestExternalities::from(storage).execute_with(|| {
+			let set_id = Grandpa::current_set_id();
+			let signed_prevote = |target_hash| {
+				let prevote = finality_grandpa::Prevote { target_hash, target_number: 1 };
+				let message = finality_grandpa::Message::Prevote(prevote.clone());
+				let signature = grandpa.sign(&localized_payload(1, set_id, &message));
+				(prevote, AuthoritySignature::from(signature))
+			};
+			// Two different prevotes in the same round.
+			let equivocation_proof = EquivocationProof::new(
+				set_id,
+				Equivocation::Prevote(finality_grandpa::Equivocation {
+					round_number: 1,
+					identity: grandpa.public().into(),
+					first: signed_prevote(Hash::repeat_byte(1)),
+					second: signed_prevote(Hash::repeat_byte(2)),
+				}),
+			);
+			// The proof handed out by `GrandpaApi::generate_key_ownership_proof`.
+			let key_owner_proof =
+				Historical::prove((KEY_TYPE, GrandpaId::from(grandpa.public()))).unwrap();
+
+			assert_ok!(Grandpa::report_equivocation_unsigned(
+				RuntimeOrigin::none(),
+				Box::new(equivocation_proof),
+				key_owner_proof,
+			));
+
+			// The only validator equivocated, so its whole bond is slashed once the slash is due.
+			let active_era = Staking::active_era().unwrap().index;
+			let slashes = pallet_staking::UnappliedSlashes::<Runtime>::get(
+				active_era + SlashDeferDuration::get(),
+			);
+			assert_eq!(slashes.len(), 1);
+			assert_eq!(slashes[0].validator, stash);
+			assert_eq!(slashes[0].own, bond);
+		});
+	}
+
+	#[test]
+	fn validators_pending_rotation_are_not_reported() {
+		use frame_support::assert_ok;
+		use pallet_im_online::UnresponsivenessOffence;
+		use sp_core::{ed25519, sr25519, Pair};
+		use sp_staking::offence::ReportOffence;
+
+		type Reporter = <Runtime as pallet_im_online::Config>::ReportUnresponsiveness;
+		let stash = AccountId::new([1; 32]);
+		let bond = MIN_VALIDATOR_BOND;
+		// The keys `UpgradeSessionKeys` registers: the Aura key is reused for im-online.
+		let aura = sr25519::Pair::from_string("//Alice", None).unwrap().public();
+		let keys = SessionKeys {
+			aura: aura.into(),
+			grandpa: ed25519::Pair::from_string("//Alice", None).unwrap().public().into(),
+			im_online: aura.into(),
+			authority_discovery: aura.into(),
+		};
+		let storage = GenesisConfig {
+			balances: BalancesConfig { balances: vec![(stash.clone(), 2 * bond)] },
+			session: SessionConfig { keys: vec![(stash.clone(), stash.clone(), keys)] },
+			staking: StakingConfig {
+				validator_count: 1,
+				minimum_validator_count: 1,
+				stakers: vec![(stash.clone(), stash.clone(), bond, StakerStatus::Validator)],
+				..Default::default()
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+
+		sp_io::TestExternalities::from(storage).execute_with(|| {
+			assert_eq!(migrations::validators_pending_rotation(), vec![stash.clone()]);
+
+			let exposure = pallet_staking::Exposure { total: bond, own: bond, others: vec![] };
+			let offence = UnresponsivenessOffence {
+				session_index: Session::current_index(),
+				validator_set_count: 1,
+				offenders: vec![(stash.clone(), exposure)],
+			};
+			assert_ok!(Reporter::report_offence(vec![], offence));
+			assert_eq!(pallet_offences::Reports::<Runtime>::iter().count(), 0);
+		});
+	}
+
+	#[test]
+	fn fee_split_adds_up_to_fees() {
+		new_test_ext(DefaultBurnThreshold::get() + UNIT).execute_with(|| {
+			for fees in [0, 1, 7, 999, 1_000_001, 123_456_789_013, u64::MAX as Balance] {
+				let (author, treasury, burn) = split_fees(fees);
+				assert_eq!(author + treasury + burn, fees);
+			}
+		});
+	}
+
+	#[test]
+	fn fees_are_burned_above_threshold() {
+		new_test_ext(DefaultBurnThreshold::get() + UNIT).execute_with(|| {
+			assert_eq!(split_fees(8 * UNIT), (6 * UNIT, UNIT, UNIT));
+		});
+	}
+
+	#[test]
+	fn fees_are_not_burned_below_threshold() {
+		new_test_ext(UNIT).execute_with(|| {
+			assert_eq!(split_fees(8 * UNIT), (6 * UNIT, 2 * UNIT, 0));
+		});
+	}
+
+	#[test]
+	fn pending_multisig_operations_list_approvals() {
+		use frame_support::assert_ok;
+
+		new_test_ext(1_000 * UNIT).execute_with(|| {
+			System::set_block_number(1);
+			let [alice, bob, charlie] = [1, 2, 3].map(|i| AccountId::new([i; 32]));
+			let multisig =
+				Multisig::multi_account_id(&[alice.clone(), bob.clone(), charlie.clone()], 3);
+			let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+			let call_hash = call.using_encoded(sp_io::hashing::blake2_256);
+			let max_weight = call.get_dispatch_info().weight;
+			let timepoint = Multisig::timepoint();
+
+			assert_ok!(Multisig::as_multi(
+				RuntimeOrigin::signed(alice.clone()),
+				3,
+				vec![bob.clone(), charlie.clone()],
+				None,
+				Box::new(call),
+				max_weight,
+			));
+			assert_ok!(Multisig::approve_as_multi(
+				RuntimeOrigin::signed(bob.clone()),
+				3,
+				vec![alice.clone(), charlie],
+				Some(timepoint),
+				call_hash,
+				max_weight,
+			));
+
+			assert_eq!(
+				pending_multisig_operations(&multisig),
+				vec![multisig_api::PendingOperation {
+					call_hash: call_hash.into(),
+					height: timepoint.height,
+					index: timepoint.index,
+					depositor: alice.clone(),
+					deposit: DepositBase::get() + 3 * DepositFactor::get(),
+					approvals: vec![alice.clone(), bob],
+				}]
+			);
+			assert!(pending_multisig_operations(&alice).is_empty());
+		});
+	}
+
+	#[test]
+	fn proxy_types_filter_calls() {
+		use sp_runtime::MultiAddress;
+		use ProxyType::*;
+
+		let bob = MultiAddress::Id(AccountId::new([2; 32]));
+		let proxy_types =
+			[Any, NonTransfer, Governance, Staking, Assets, Nfts, Contracts, IdentityJudgement];
+		// Whether each of `proxy_types`, in order, may dispatch the call.
+		let cases = [
+			(
+				RuntimeCall::Balances(pallet_balances::Call::transfer {
+					dest: bob.clone(),
+					value: 1,
+				}),
+				[true, false, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Indices(pallet_indices::Call::transfer { new: bob.clone(), index: 0 }),
+				[true, false, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Vesting(pallet_vesting::Call::vested_transfer {
+					target: bob.clone(),
+					schedule: pallet_vesting::VestingInfo::new(100 * UNIT, UNIT, 0),
+				}),
+				[true, false, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Recovery(pallet_recovery::Call::create_recovery {
+					friends: vec![AccountId::new([2; 32])],
+					threshold: 1,
+					delay_period: 0,
+				}),
+				[true, false, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Recovery(pallet_recovery::Call::initiate_recovery {
+					account: bob.clone(),
+				}),
+				[true, false, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::EvmAccounts(pallet_evm_accounts::Call::claim_default_address {}),
+				[true, false, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Recovery(pallet_recovery::Call::remove_recovery {}),
+				[true, true, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::System(frame_system::Call::remark { remark: vec![] }),
+				[true, true, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Vesting(pallet_vesting::Call::vest {}),
+				[true, true, false, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Utility(pallet_utility::Call::batch { calls: vec![] }),
+				[true, true, true, true, true, true, true, true],
+			),
+			(
+				RuntimeCall::Democracy(pallet_democracy::Call::second { proposal: 0 }),
+				[true, true, true, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Council(pallet_collective::Call::vote {
+					proposal: H256::zero(),
+					index: 0,
+					approve: true,
+				}),
+				[true, true, true, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Treasury(pallet_treasury::Call::propose_spend {
+					value: 1,
+					beneficiary: bob.clone(),
+				}),
+				[true, true, true, false, false, false, false, false],
+			),
+			(
+				RuntimeCall::Staking(pallet_staking::Call::chill {}),
+				[true, true, false, true, false, false, false, false],
+			),
+			(
+				RuntimeCall::Session(pallet_session::Call::purge_keys {}),
+				[true, true, false, true, false, false, false, false],
+			),
+			(
+				RuntimeCall::VoterList(pallet_bags_list::Call::rebag { dislocated: bob.clone() }),
+				[true, true, false, true, false, false, false, false],
+			),
+			(
+				RuntimeCall::Assets(pallet_assets::Call::freeze_asset { id: 0.into() }),
+				[true, false, false, false, true, false, false, false],
+			),
+			(
+				RuntimeCall::Nfts(pallet_nfts::Call::lock_item_transfer { collection: 0, item: 0 }),
+				[true, false, false, false, false, true, false, false],
+			),
+			(
+				RuntimeCall::Contracts(pallet_contracts::Call::remove_code {
+					code_hash: H256::zero(),
+				}),
+				[true, false, false, false, false, false, true, false],
+			),
+			(
+				RuntimeCall::Identity(pallet_identity::Call::provide_judgement {
+					reg_index: 0,
+					target: bob.clone(),
+					judgement: pallet_identity::Judgement::Reasonable,
+					identity: H256::zero(),
+				}),
+				[true, true, false, false, false, false, false, true],
+			),
+			(
+				RuntimeCall::Identity(pallet_identity::Call::clear_identity {}),
+				[true, true, false, false, false, false, false, false],
+			),
+		];
+
+		for (call, allowed) in cases {
+			for (proxy_type, allowed) in proxy_types.iter().zip(allowed) {
+				assert_eq!(
+					proxy_type.filter(&call),
+					allowed,
+					"{:?} proxy for {:?}",
+					proxy_type,
+					call
+				);
+			}
+		}
+
+		// A proxy may only add proxies allowed to do less than itself.
+		for proxy_type in proxy_types {
+			assert!(Any.is_superset(&proxy_type));
+			assert_eq!(proxy_type.is_superset(&Any), proxy_type == Any);
+		}
+		assert!(NonTransfer.is_superset(&Staking));
+		assert!(!NonTransfer.is_superset(&Assets));
+		assert!(!Staking.is_superset(&Governance));
+	}
+
+	#[test]
+	fn announced_proxy_calls_wait_for_the_delay() {
+		use frame_support::{assert_noop, assert_ok};
+		use sp_runtime::traits::Hash as _;
+
+		new_test_ext(1_000 * UNIT).execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::new([1; 32]);
+			let bob = AccountId::new([2; 32]);
+			let charlie = AccountId::new([3; 32]);
+			assert_ok!(Balances::transfer(
+				RuntimeOrigin::signed(alice.clone()),
+				bob.clone().into(),
+				10 * UNIT,
+			));
+			assert_ok!(Proxy::add_proxy(
+				RuntimeOrigin::signed(alice.clone()),
+				bob.clone().into(),
+				ProxyType::Any,
+				10,
+			));
+
+			let call = Box::new(RuntimeCall::Balances(pallet_balances::Call::transfer {
+				dest: charlie.clone().into(),
+				value: UNIT,
+			}));
+			assert_noop!(
+				Proxy::proxy(
+					RuntimeOrigin::signed(bob.clone()),
+					alice.clone().into(),
+					None,
+					call.clone(),
+				),
+				pallet_proxy::Error::<Runtime>::Unannounced,
+			);
+
+			let call_hash = BlakeTwo256::hash_of(&call);
+			assert_ok!(Proxy::announce(
+				RuntimeOrigin::signed(bob.clone()),
+				alice.clone().into(),
+				call_hash,
+			));
+			let execute = || {
+				Proxy::proxy_announced(
+					RuntimeOrigin::signed(charlie.clone()),
+					bob.clone().into(),
+					alice.clone().into(),
+					None,
+					call.clone(),
+				)
+			};
+			System::set_block_number(10);
+			assert_noop!(execute(), pallet_proxy::Error::<Runtime>::Unannounced);
+
+			System::set_block_number(11);
+			assert_ok!(execute());
+			assert_eq!(Balances::free_balance(&charlie), UNIT);
+		});
+	}
+
+	#[test]
+	fn pure_proxy_is_controlled_by_its_spawner() {
+		use frame_support::assert_ok;
+
+		new_test_ext(1_000 * UNIT).execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::new([1; 32]);
+			let bob = AccountId::new([2; 32]);
+			assert_ok!(Proxy::create_pure(
+				RuntimeOrigin::signed(alice.clone()),
+				ProxyType::Any,
+				0,
+				0,
+			));
+			let pure = Proxy::pure_account(&alice, &ProxyType::Any, 0, None);
+			assert_eq!(
+				Balances::reserved_balance(&alice),
+				ProxyDepositBase::get() + ProxyDepositFactor::get()
+			);
+
+			assert_ok!(Balances::transfer(
+				RuntimeOrigin::signed(alice.clone()),
+				pure.clone().into(),
+				10 * UNIT,
+			));
+			assert_ok!(Proxy::proxy(
+				RuntimeOrigin::signed(alice),
+				pure.clone().into(),
+				None,
+				Box::new(RuntimeCall::Balances(pallet_balances::Call::transfer {
+					dest: bob.clone().into(),
+					value: UNIT,
+				})),
+			));
+			assert_eq!(Balances::free_balance(&bob), UNIT);
+			assert_eq!(Balances::free_balance(&pure), 9 * UNIT);
+		});
+	}
+
+	#[test]
+	fn vesting_unlocks_after_the_cliff() {
+		use frame_support::{assert_noop, assert_ok};
+
+		let alice = AccountId::new([1; 32]);
+		let bob = AccountId::new([2; 32]);
+		let charlie = AccountId::new([3; 32]);
+		let mut ext = new_test_ext(1_000 * UNIT);
+		// Like the genesis allocations: nothing unlocks before block 100, then linearly until 300.
+		ext.execute_with(|| {
+			pallet_vesting::GenesisConfig::<Runtime> { vesting: vec![(alice.clone(), 100, 200, 0)] }
+				.build();
+		});
+
+		ext.execute_with(|| {
+			let total = 1_000 * UNIT;
+			System::set_block_number(50);
+			assert_eq!(
+				vesting_balance(&alice),
+				Some(vesting_api::VestingBalance { total, locked: total, vested: 0 })
+			);
+			assert_eq!(vesting_balance(&bob), None);
+			assert_noop!(
+				Balances::transfer(RuntimeOrigin::signed(alice.clone()), bob.clone().into(), UNIT),
+				pallet_balances::Error::<Runtime>::LiquidityRestrictions,
+			);
+
+			System::set_block_number(200);
+			assert_eq!(
+				vesting_balance(&alice),
+				Some(vesting_api::VestingBalance { total, locked: total / 2, vested: total / 2 })
+			);
+			assert_ok!(Vesting::vest(RuntimeOrigin::signed(alice.clone())));
+
+			// Grants after genesis go through `vested_transfer`.
+			let schedule = pallet_vesting::VestingInfo::new(200 * UNIT, 2 * UNIT, 250);
+			assert_ok!(Vesting::vested_transfer(
+				RuntimeOrigin::signed(alice),
+				charlie.clone().into(),
+				schedule,
+			));
+			System::set_block_number(300);
+			assert_eq!(
+				vesting_balance(&charlie),
+				Some(vesting_api::VestingBalance {
+					total: 200 * UNIT,
+					locked: 100 * UNIT,
+					vested: 100 * UNIT,
+				})
+			);
+		});
+	}
+
+	#[test]
+	fn nft_enumeration_pages_with_start_keys() {
+		use frame_support::assert_ok;
+
+		let alice = AccountId::new([1; 32]);
+		new_test_ext(1_000 * UNIT).execute_with(|| {
+			let origin = RuntimeOrigin::signed(alice.clone());
+			let mut owned = Vec::new();
+			for collection in 0..2 {
+				assert_ok!(Nfts::create(origin.clone(), alice.clone().into(), Default::default()));
+				for item in 0..3 {
+					let owner = alice.clone().into();
+					assert_ok!(Nfts::mint(origin.clone(), collection, item, owner, None));
+					owned.push((collection, item));
+				}
+			}
+			// Keys are hashed, so pages follow storage order rather than id order.
+			let mut paged = Vec::new();
+			let mut start_key = None;
+			loop {
+				let page = owned_items(&alice, start_key, 4);
+				start_key = page.last().copied();
+				paged.extend(page);
+				if start_key.is_none() {
+					break
+				}
+			}
+			assert_eq!(paged.len(), owned.len());
+			paged.sort();
+			assert_eq!(paged, owned);
+
+			let first = collection_items(1, None, 2);
+			assert_eq!(first.len(), 2);
+			let mut items = [first.clone(), collection_items(1, first.last().copied(), 2)].concat();
+			items.sort();
+			assert_eq!(items, vec![0, 1, 2]);
+			assert_eq!(owned_items(&AccountId::new([2; 32]), None, 4), vec![]);
+		});
+	}
+
+	#[test]
+	fn nomination_pools_limits_are_configured_once() {
+		use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+		use pallet_nomination_pools::{MaxPools, MinCreateBond, MinJoinBond};
+
+		new_test_ext(0).execute_with(|| {
+			assert_eq!(MaxPools::<Runtime>::get(), None);
+
+			migrations::ConfigureNominationPools::on_runtime_upgrade();
+			assert_eq!(MinJoinBond::<Runtime>::get(), MIN_POOL_JOIN_BOND);
+			assert_eq!(MinCreateBond::<Runtime>::get(), MIN_POOL_CREATE_BOND);
+			assert_eq!(MaxPools::<Runtime>::get(), Some(constants::pools::MAX_POOLS));
+			assert_eq!(
+				NominationPools::on_chain_storage_version(),
+				NominationPools::current_storage_version()
+			);
+
+			// Governance may change the limits afterwards.
+			MaxPools::<Runtime>::put(1);
+			migrations::ConfigureNominationPools::on_runtime_upgrade();
+			assert_eq!(MaxPools::<Runtime>::get(), Some(1));
+		});
+	}
+
+	#[test]
+	fn evm_chain_id_is_set_once() {
+		use frame_support::traits::{ConstU64, OnRuntimeUpgrade};
+
+		type SetEvmChainId = migrations::SetEvmChainId<ConstU64<{ constants::evm::CHAIN_ID }>>;
+
+		new_test_ext(0).execute_with(|| {
+			assert_eq!(EVMChainId::chain_id(), 0);
+			SetEvmChainId::on_runtime_upgrade();
+			assert_eq!(<Runtime as pallet_evm::Config>::ChainId::get(), constants::evm::CHAIN_ID);
+
+			pallet_evm_chain_id::ChainId::<Runtime>::put(constants::evm::TESTNET_CHAIN_ID);
+			SetEvmChainId::on_runtime_upgrade();
+			assert_eq!(EVMChainId::chain_id(), constants::evm::TESTNET_CHAIN_ID);
+		});
+	}
+
+	#[test]
+	fn council_replaces_sudo() {
+		use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade};
+
+		let mut ext = new_test_ext(1_000 * UNIT);
+		ext.execute_with(|| {
+			let key = AccountId::new([1; 32]);
+			let members = |count: u8| (1..=count).map(|i| AccountId::new([i; 32])).collect::<Vec<_>>();
+			pallet_sudo::GenesisConfig::<Runtime> { key: Some(key.clone()) }.build();
+			pallet_collective::Members::<Runtime, CouncilCollective>::put(members(5));
+			pallet_collective::Members::<Runtime, TechnicalCollective>::put(members(3));
+			assert!(migrations::origins_needing_sudo().is_empty());
+
+			// Only referenda change who sits in the collectives.
+			let council_majority = RuntimeOrigin::from(
+				pallet_collective::RawOrigin::<AccountId, CouncilCollective>::Members(3, 5),
+			);
+			assert!(Council::set_members(council_majority, members(1), None, 5).is_err());
+
+			migrations::RemoveSudoKey::on_runtime_upgrade();
+			assert_eq!(Sudo::key(), None);
+			assert_eq!(Sudo::on_chain_storage_version(), migrations::SUDO_REMOVED);
+
+			// The migration runs once, so a key set again afterwards is kept.
+			pallet_sudo::GenesisConfig::<Runtime> { key: Some(key.clone()) }.build();
+			migrations::RemoveSudoKey::on_runtime_upgrade();
+			assert_eq!(Sudo::key(), Some(key));
+		});
+	}
+
 	#[test]
 	fn check_whitelist() {
 		let whitelist: HashSet<String> = AllPalletsWithSystem::whitelisted_storage_keys()
@@ -0,0 +1,535 @@
+//! Runtime migrations, run by `Executive` on the first block after an upgrade.
+//!
+//! Migrations are one-off: remove them from [`Migrations`] once the release that carries them
+//! has been enacted on every network.
+
+use crate::{
+	constants::{currency::*, evm, pools},
+	AccountId, Aura, AuthorityDiscovery, Balances, EVMChainId, Grandpa, ImOnline, NominationPools,
+	Runtime, RuntimeBlockWeights, Session, SessionKeys, Treasury,
+};
+use frame_support::{
+	storage::migration::take_storage_value,
+	traits::{
+		fungible::Inspect, ConstU64, Currency, ExistenceRequirement, Get, GetStorageVersion,
+		OnRuntimeUpgrade, OneSessionHandler, StorageVersion,
+	},
+	weights::Weight,
+};
+use hex_literal::hex;
+use pallet_im_online::UnresponsivenessOffence;
+use pallet_session::historical::IdentificationTuple;
+use sp_core::sr25519;
+use sp_staking::{
+	offence::{OffenceError, ReportOffence},
+	SessionIndex,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// All migrations applied on the next runtime upgrade.
+///
+/// Releases for the testnet use `evm::TESTNET_CHAIN_ID` in [`SetEvmChainId`] instead.
+/// [`RemoveSudoKey`] is only added to the release chosen for removing sudo.
+pub type Migrations = (
+	MigrateLegacyTreasury,
+	UpgradeSessionKeys,
+	ConfigureNominationPools,
+	SetEvmChainId<ConstU64<{ evm::CHAIN_ID }>>,
+);
+
+/// The account that collected the treasury share of fees, staking slashes and reward remainders
+/// before they were routed to `pallet_treasury`.
+pub const LEGACY_TREASURY: [u8; 32] =
+	hex!["2c11d2aff81147e5522539c51c1cb87bae94a0865d214f3983f3557a6732f26a"];
+
+/// Moves the transferable balance of [`LEGACY_TREASURY`] into the pot of `pallet_treasury`, where
+/// spending it goes through the treasury's approval process.
+///
+/// Locked and reserved funds cannot be moved and stay in the legacy account, which the migration
+/// logs so they can be released and moved by governance.
+pub struct MigrateLegacyTreasury;
+
+/// The part of the balance of `who` that can be transferred away.
+fn transferable(who: &AccountId) -> crate::Balance {
+	<Balances as Inspect<AccountId>>::reducible_balance(who, false)
+}
+
+impl OnRuntimeUpgrade for MigrateLegacyTreasury {
+	fn on_runtime_upgrade() -> Weight {
+		let legacy = AccountId::from(LEGACY_TREASURY);
+		let pot = Treasury::account_id();
+		let amount = transferable(&legacy);
+		let remainder = Balances::total_balance(&legacy).saturating_sub(amount);
+		if remainder > 0 {
+			log::warn!(
+				target: "runtime::migrations",
+				"{} is locked or reserved and stays in the legacy treasury account",
+				remainder,
+			);
+		}
+
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		if amount == 0 {
+			log::info!(target: "runtime::migrations", "nothing to move from the legacy treasury");
+			return db_weight.reads(2)
+		}
+
+		match Balances::transfer(&legacy, &pot, amount, ExistenceRequirement::AllowDeath) {
+			Ok(()) => log::info!(
+				target: "runtime::migrations",
+				"moved {} from the legacy treasury account to the treasury pot",
+				amount,
+			),
+			Err(err) => log::error!(
+				target: "runtime::migrations",
+				"failed to move the legacy treasury balance: {:?}",
+				err,
+			),
+		}
+
+		db_weight.reads_writes(4, 3)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		use codec::Encode;
+
+		let legacy = AccountId::from(LEGACY_TREASURY);
+		let pot_balance = Balances::free_balance(&Treasury::account_id());
+		Ok((pot_balance, transferable(&legacy)).encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		use crate::Balance;
+		use codec::Decode;
+
+		let (pot_before, legacy_before) = <(Balance, Balance)>::decode(&mut &state[..])
+			.map_err(|_| "failed to decode the pre upgrade state")?;
+		let legacy = AccountId::from(LEGACY_TREASURY);
+
+		frame_support::ensure!(
+			transferable(&legacy) == 0,
+			"legacy treasury account still holds transferable funds"
+		);
+		frame_support::ensure!(
+			Balances::free_balance(&Treasury::account_id()) == pot_before + legacy_before,
+			"treasury pot did not receive the legacy balance"
+		);
+		Ok(())
+	}
+}
+
+sp_runtime::impl_opaque_keys! {
+	/// The session keys before the im-online and authority discovery keys were added.
+	pub struct OldSessionKeys {
+		pub aura: Aura,
+		pub grandpa: Grandpa,
+	}
+}
+
+/// Adds the im-online and authority discovery keys to the session keys of every validator.
+///
+/// Both are sr25519 keys like the Aura key, which is registered for them as a placeholder. The
+/// keystores of the validators only hold its secret under the Aura key type, so every validator
+/// has to call `author_rotateKeys` on its node and submit the result with `session.setKeys`.
+/// Until the new keys are active it is listed by [`validators_pending_rotation`] and is not
+/// reported for missing heartbeats. Chain specs built before this release store the old keys as
+/// well and are upgraded the same way once they run this runtime.
+///
+/// The two pallets only learn their keys at session changes, so they are also initialised for the
+/// current session.
+pub struct UpgradeSessionKeys;
+
+impl OnRuntimeUpgrade for UpgradeSessionKeys {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		if !ImOnline::keys().is_empty() {
+			log::info!(target: "runtime::migrations", "session keys are already upgraded");
+			return db_weight.reads(1)
+		}
+
+		Session::upgrade_keys::<OldSessionKeys, _>(|_, old| {
+			let key = sr25519::Public::from(old.aura.clone());
+			SessionKeys {
+				aura: old.aura,
+				grandpa: old.grandpa,
+				im_online: key.into(),
+				authority_discovery: key.into(),
+			}
+		});
+
+		// The keys queued for the next session are the best guess for the current one; they only
+		// differ for validators that rotated their keys during this session.
+		let validators = Session::validators();
+		let keys = validators
+			.iter()
+			.filter_map(|validator| {
+				pallet_session::NextKeys::<Runtime>::get(validator).map(|keys| (validator, keys))
+			})
+			.collect::<Vec<_>>();
+		<ImOnline as OneSessionHandler<AccountId>>::on_genesis_session(
+			keys.iter().map(|(validator, keys)| (*validator, keys.im_online.clone())),
+		);
+		<AuthorityDiscovery as OneSessionHandler<AccountId>>::on_genesis_session(
+			keys.iter()
+				.map(|(validator, keys)| (*validator, keys.authority_discovery.clone())),
+		);
+		log::info!(
+			target: "runtime::migrations",
+			"upgraded the session keys of {} validators",
+			validators.len(),
+		);
+		log::warn!(
+			target: "runtime::migrations",
+			"validators that have to rotate their session keys: {:?}",
+			validators_pending_rotation(),
+		);
+
+		// Every key of every validator is rewritten, so make sure nothing else fits in the block.
+		RuntimeBlockWeights::get().max_block
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		let validators = Session::validators();
+		for validator in &validators {
+			let keys = pallet_session::NextKeys::<Runtime>::get(validator)
+				.ok_or("validator without session keys")?;
+			frame_support::ensure!(
+				sr25519::Public::from(keys.im_online) == sr25519::Public::from(keys.aura),
+				"im-online key was not derived from the aura key"
+			);
+		}
+		frame_support::ensure!(
+			ImOnline::keys().len() == validators.len(),
+			"im-online keys were not initialised for the current session"
+		);
+		frame_support::ensure!(
+			AuthorityDiscovery::authorities().len() == validators.len(),
+			"authority discovery keys were not initialised for the current session"
+		);
+		Ok(())
+	}
+}
+
+/// The validators of the current session whose im-online key is still the placeholder that
+/// [`UpgradeSessionKeys`] copied from their Aura key.
+///
+/// Their nodes cannot sign heartbeats until they have rotated their keys and the new keys became
+/// active, two sessions after `session.setKeys`.
+pub fn validators_pending_rotation() -> Vec<AccountId> {
+	let aura = Aura::authorities();
+	let im_online = ImOnline::keys();
+	Session::validators()
+		.into_iter()
+		.zip(aura.iter().zip(im_online.iter()))
+		.filter(|(_, (aura, im_online))| {
+			sr25519::Public::from((*aura).clone()) == sr25519::Public::from((*im_online).clone())
+		})
+		.map(|(validator, _)| validator)
+		.collect()
+}
+
+type Unresponsiveness = UnresponsivenessOffence<IdentificationTuple<Runtime>>;
+
+/// Passes the unresponsiveness reports of `pallet_im_online` on to `R`, leaving out the validators
+/// in [`validators_pending_rotation`].
+///
+/// Remove it together with [`UpgradeSessionKeys`] once every validator has rotated its keys.
+pub struct IgnorePendingRotation<R>(PhantomData<R>);
+
+impl<R> ReportOffence<AccountId, IdentificationTuple<Runtime>, Unresponsiveness>
+	for IgnorePendingRotation<R>
+where
+	R: ReportOffence<AccountId, IdentificationTuple<Runtime>, Unresponsiveness>,
+{
+	fn report_offence(
+		reporters: Vec<AccountId>,
+		mut offence: Unresponsiveness,
+	) -> Result<(), OffenceError> {
+		let pending = validators_pending_rotation();
+		if !pending.is_empty() {
+			log::warn!(
+				target: "runtime::migrations",
+				"not reporting validators that have to rotate their session keys: {:?}",
+				pending,
+			);
+			offence.offenders.retain(|(validator, _)| !pending.contains(validator));
+		}
+		if offence.offenders.is_empty() {
+			return Ok(())
+		}
+		R::report_offence(reporters, offence)
+	}
+
+	fn is_known_offence(
+		offenders: &[IdentificationTuple<Runtime>],
+		time_slot: &SessionIndex,
+	) -> bool {
+		R::is_known_offence(offenders, time_slot)
+	}
+}
+
+/// Writes the limits of `pallet_nomination_pools` that new chains get from their chain spec.
+///
+/// The pallet only sets them at genesis, so a chain that adds it in an upgrade would otherwise
+/// start with no minimum bonds and no limit on pools or members. The pallet's storage version is
+/// set along with them, which marks the migration as done.
+pub struct ConfigureNominationPools;
+
+impl OnRuntimeUpgrade for ConfigureNominationPools {
+	fn on_runtime_upgrade() -> Weight {
+		use pallet_nomination_pools::{
+			MaxPoolMembers, MaxPoolMembersPerPool, MaxPools, MinCreateBond, MinJoinBond,
+		};
+
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let current = NominationPools::current_storage_version();
+		if NominationPools::on_chain_storage_version() == current {
+			log::info!(target: "runtime::migrations", "nomination pools are already configured");
+			return db_weight.reads(1)
+		}
+
+		MinJoinBond::<Runtime>::put(MIN_POOL_JOIN_BOND);
+		MinCreateBond::<Runtime>::put(MIN_POOL_CREATE_BOND);
+		MaxPools::<Runtime>::put(pools::MAX_POOLS);
+		MaxPoolMembersPerPool::<Runtime>::put(pools::MAX_MEMBERS_PER_POOL);
+		MaxPoolMembers::<Runtime>::put(pools::MAX_MEMBERS);
+		current.put::<NominationPools>();
+		log::info!(target: "runtime::migrations", "configured the nomination pools limits");
+
+		db_weight.reads_writes(1, 6)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		frame_support::ensure!(
+			NominationPools::on_chain_storage_version() ==
+				NominationPools::current_storage_version(),
+			"nomination pools storage version was not set"
+		);
+		frame_support::ensure!(
+			pallet_nomination_pools::MaxPools::<Runtime>::get().is_some(),
+			"nomination pools are not limited"
+		);
+		Ok(())
+	}
+}
+
+/// Sets the EIP-155 chain id of the EVM to `Id`.
+///
+/// `pallet_evm_chain_id` only sets it at genesis, so a chain that adds the pallet in an upgrade
+/// would run the EVM with chain id 0, under which transactions signed for any other chain without
+/// replay protection are valid. A chain id that is already set is kept.
+pub struct SetEvmChainId<Id>(PhantomData<Id>);
+
+impl<Id: Get<u64>> OnRuntimeUpgrade for SetEvmChainId<Id> {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let chain_id = EVMChainId::chain_id();
+		if chain_id != 0 {
+			log::info!(target: "runtime::migrations", "EVM chain id is already {}", chain_id);
+			return db_weight.reads(1)
+		}
+
+		pallet_evm_chain_id::ChainId::<Runtime>::put(Id::get());
+		log::info!(target: "runtime::migrations", "set the EVM chain id to {}", Id::get());
+
+		db_weight.reads_writes(1, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		frame_support::ensure!(EVMChainId::chain_id() != 0, "EVM chain id is not set");
+		Ok(())
+	}
+}
+
+/// Removes the key of `pallet_sudo`, leaving root calls to referenda.
+///
+/// Most origins that used to be root only also accept the council or the technical committee, so
+/// the release carrying this migration is only enacted once both have members, which
+/// `pre_upgrade` checks. The migration runs once: it sets the storage version of `pallet_sudo` to
+/// [`SUDO_REMOVED`], and does nothing on a chain that already has it.
+pub struct RemoveSudoKey;
+
+/// The storage version of `pallet_sudo` once [`RemoveSudoKey`] has run.
+pub const SUDO_REMOVED: StorageVersion = StorageVersion::new(1);
+
+impl OnRuntimeUpgrade for RemoveSudoKey {
+	fn on_runtime_upgrade() -> Weight {
+		use crate::Sudo;
+
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		if Sudo::on_chain_storage_version() >= SUDO_REMOVED {
+			log::info!(target: "runtime::migrations", "sudo key is already removed");
+			return db_weight.reads(1)
+		}
+
+		if let Some(key) = take_storage_value::<AccountId>(b"Sudo", b"Key", &[]) {
+			log::info!(target: "runtime::migrations", "removed the sudo key {:?}", key);
+		}
+		SUDO_REMOVED.put::<Sudo>();
+
+		db_weight.reads_writes(2, 2)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		use crate::{Council, Sudo, TechnicalCommittee};
+
+		if Sudo::on_chain_storage_version() >= SUDO_REMOVED {
+			return Ok(Vec::new())
+		}
+		frame_support::ensure!(
+			!Council::members().is_empty(),
+			"the council has no members to take over from sudo"
+		);
+		frame_support::ensure!(
+			!TechnicalCommittee::members().is_empty(),
+			"the technical committee has no members to take over from sudo"
+		);
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		use crate::Sudo;
+
+		frame_support::ensure!(Sudo::key().is_none(), "sudo key is still set");
+		frame_support::ensure!(
+			Sudo::on_chain_storage_version() >= SUDO_REMOVED,
+			"sudo storage version was not set"
+		);
+
+		let origins = origins_needing_sudo();
+		if !origins.is_empty() {
+			log::error!(target: "runtime::migrations", "only root can use {:?}", origins);
+			return Err("some origins can only be used by root")
+		}
+		Ok(())
+	}
+}
+
+/// The origins that used to be root only and that the council or the technical committee cannot
+/// use, at their current sizes, with the share of aye votes the runtime asks for.
+///
+/// Some origins are left to root, which referenda still reach:
+/// - the blacklist origin of `pallet_democracy`: blacklisting bans a proposal for good, while the
+///   technical committee can already cancel one unanimously or veto it for the cool-off period;
+/// - the members of the council, the technical committee and the alliance: there is no elections
+///   pallet, so a council setting them could rewrite its own membership and keep itself in place.
+#[cfg(any(feature = "try-runtime", test))]
+pub fn origins_needing_sudo() -> Vec<&'static str> {
+	use crate::{
+		Council, CouncilCollective, RuntimeOrigin, TechnicalCollective, TechnicalCommittee,
+	};
+	use frame_support::traits::EnsureOrigin;
+	use pallet_collective::RawOrigin;
+
+	/// The fewest ayes out of `members` votes that make a share of at least `num / den`.
+	fn at_least(members: u32, num: u32, den: u32) -> u32 {
+		(members * num + den - 1) / den
+	}
+
+	// A motion passed by `ayes` out of all members, where a majority is more than half of them.
+	let councillors = Council::members().len() as u32;
+	let council = |ayes| {
+		RuntimeOrigin::from(RawOrigin::<AccountId, CouncilCollective>::Members(ayes, councillors))
+	};
+	let council_majority = || council(councillors / 2 + 1);
+	let technical_members = TechnicalCommittee::members().len() as u32;
+	let technical_committee = |ayes| {
+		RuntimeOrigin::from(RawOrigin::<AccountId, TechnicalCollective>::Members(
+			ayes,
+			technical_members,
+		))
+	};
+
+	[
+		(
+			"staking admin",
+			<Runtime as pallet_staking::Config>::AdminOrigin::try_origin(council(at_least(
+				councillors,
+				3,
+				4,
+			)))
+			.is_ok(),
+		),
+		(
+			"election force",
+			<Runtime as pallet_election_provider_multi_phase::Config>::ForceOrigin::try_origin(
+				council_majority(),
+			)
+			.is_ok(),
+		),
+		(
+			"assets force",
+			<Runtime as pallet_assets::Config>::ForceOrigin::try_origin(council_majority()).is_ok(),
+		),
+		(
+			"nfts force",
+			<Runtime as pallet_nfts::Config>::ForceOrigin::try_origin(council_majority()).is_ok(),
+		),
+		(
+			"identity force",
+			<Runtime as pallet_identity::Config>::ForceOrigin::try_origin(council_majority())
+				.is_ok(),
+		),
+		(
+			"identity registrar",
+			<Runtime as pallet_identity::Config>::RegistrarOrigin::try_origin(council_majority())
+				.is_ok(),
+		),
+		(
+			"fee split update",
+			<Runtime as pallet_fee_split::Config>::UpdateOrigin::try_origin(council_majority())
+				.is_ok(),
+		),
+		(
+			"scheduler",
+			<Runtime as pallet_scheduler::Config>::ScheduleOrigin::try_origin(council_majority())
+				.is_ok(),
+		),
+		(
+			"preimage manager",
+			<Runtime as pallet_preimage::Config>::ManagerOrigin::try_origin(technical_committee(
+				technical_members / 2 + 1,
+			))
+			.is_ok(),
+		),
+		(
+			"treasury spend",
+			<Runtime as pallet_treasury::Config>::SpendOrigin::try_origin(council(at_least(
+				councillors,
+				3,
+				5,
+			)))
+			.is_ok(),
+		),
+		(
+			"democracy cancel proposal",
+			<Runtime as pallet_democracy::Config>::CancelProposalOrigin::try_origin(
+				technical_committee(technical_members),
+			)
+			.is_ok(),
+		),
+		(
+			"democracy cancellation",
+			<Runtime as pallet_democracy::Config>::CancellationOrigin::try_origin(council(
+				at_least(councillors, 2, 3),
+			))
+			.is_ok(),
+		),
+	]
+	.into_iter()
+	.filter(|(_, accepted)| !accepted)
+	.map(|(origin, _)| origin)
+	.collect()
+}
@@ -0,0 +1,41 @@
+//! Runtime API definition for multisig operations.
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// A multisig operation that has not collected enough approvals yet.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct PendingOperation<AccountId, Balance, BlockNumber> {
+	/// Hash of the call dispatched once the threshold is reached.
+	pub call_hash: H256,
+	/// Block in which the operation was opened.
+	pub height: BlockNumber,
+	/// Index of the opening extrinsic in that block. Together with `height`, it is the timepoint
+	/// the other signatories pass to approve or cancel the operation.
+	pub index: u32,
+	/// The signatory that opened the operation and holds its deposit.
+	pub depositor: AccountId,
+	/// Deposit reserved from the depositor until the operation is executed or cancelled.
+	pub deposit: Balance,
+	/// Signatories that approved the operation so far, sorted.
+	pub approvals: Vec<AccountId>,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait MultisigApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the pending operations of the multisig account `multisig`.
+		fn pending_operations(multisig: AccountId)
+			-> Vec<PendingOperation<AccountId, Balance, BlockNumber>>;
+
+		/// Returns the multisig account of `signatories` for `threshold`, in any order.
+		fn multisig_account(signatories: Vec<AccountId>, threshold: u16) -> AccountId;
+	}
+}
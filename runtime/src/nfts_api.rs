@@ -0,0 +1,29 @@
+//! Runtime API definition for enumerating `pallet_nfts` items.
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait NftsEnumerationApi<AccountId, CollectionId, ItemId>
+	where
+		AccountId: Codec,
+		CollectionId: Codec,
+		ItemId: Codec,
+	{
+		/// Returns up to `limit` `(CollectionId, ItemId)` pairs owned by `account`, starting after
+		/// `start_key` or from the first one if it is `None`.
+		fn owned_items(
+			account: AccountId,
+			start_key: Option<(CollectionId, ItemId)>,
+			limit: u32,
+		) -> Vec<(CollectionId, ItemId)>;
+
+		/// Returns up to `limit` `ItemId`s of `collection`, starting after `start_key` or from the
+		/// first one if it is `None`.
+		fn collection_items(
+			collection: CollectionId,
+			start_key: Option<ItemId>,
+			limit: u32,
+		) -> Vec<ItemId>;
+	}
+}
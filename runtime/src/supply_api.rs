@@ -0,0 +1,32 @@
+//! Runtime API definition for the LUNES supply.
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Breakdown of the LUNES supply.
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct SupplyInfo<Balance> {
+	/// Total amount of LUNES in existence.
+	pub total_issuance: Balance,
+	/// Balance of the treasury pot.
+	pub treasury: Balance,
+	/// Total amount burned so far. Burned LUNES are already excluded from `total_issuance`.
+	pub total_burned: Balance,
+	/// LUNES in circulation: the total issuance minus the treasury pot.
+	pub circulating: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait SupplyApi<Balance>
+	where
+		Balance: Codec,
+	{
+		/// Returns the current breakdown of the supply.
+		fn supply() -> SupplyInfo<Balance>;
+
+		/// Returns the amount burned per era for the most recent eras, oldest first.
+		fn burn_history() -> Vec<(u32, Balance)>;
+	}
+}
@@ -0,0 +1,27 @@
+//! Runtime API definition for vesting schedules.
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// How much of the vesting schedules of an account has vested.
+#[derive(Clone, Default, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct VestingBalance<Balance> {
+	/// Total amount of the account's vesting schedules.
+	pub total: Balance,
+	/// Part of `total` that is still locked.
+	pub locked: Balance,
+	/// Part of `total` that has vested. It becomes transferable once `vesting.vest` is called.
+	pub vested: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait VestingApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns the locked and vested amounts of `who`, or `None` if it has no vesting schedule.
+		fn vesting_balance(who: AccountId) -> Option<VestingBalance<Balance>>;
+	}
+}